@@ -0,0 +1,282 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::input::{self, InputSource};
+use crate::toml::{self, Table};
+use crate::{Part, StdResult, LAST_DAY};
+
+pub const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+const SOURCE: &str = "answers.toml";
+
+// A recorded answer for one part of one day, against one input file.
+// `input` is relative to the inputs directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnownAnswer {
+    pub input: String,
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+}
+
+// The answers.toml registry, a list of [[answer]] tables
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub answers: Vec<KnownAnswer>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> StdResult<Answers> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read '{}': {}", path.display(), e))?;
+        Answers::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> StdResult<Answers> {
+        let mut answers = vec![];
+
+        for table in toml::parse(text, SOURCE)? {
+            if table.name.is_none() && table.entries.is_empty() {
+                continue;
+            }
+            if table.name.as_deref() != Some("answer") || !table.array {
+                Err(format!(
+                    "{} on line {} should be [[answer]]",
+                    table.header(),
+                    table.line
+                ))?
+            }
+
+            answers.push(known_answer(&table)?);
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, input: &str, day: u32, part: Part) -> Option<&KnownAnswer> {
+        self.answers
+            .iter()
+            .find(|a| a.input == input && a.day == day && a.part == part)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from("# Known puzzle answers, checked by `aoc check`\n");
+        for answer in &self.answers {
+            toml.push_str(&format!(
+                "\n[[answer]]\ninput = {}\nday = {}\npart = {}\nanswer = {}\n",
+                toml::quote(&answer.input),
+                answer.day,
+                answer.part.number(),
+                toml::quote(&answer.answer)
+            ));
+        }
+
+        toml
+    }
+
+    pub fn save(&self, path: &Path) -> StdResult<()> {
+        fs::write(path, self.to_toml())
+            .map_err(|e| format!("couldn't write '{}': {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+fn known_answer(table: &Table) -> Result<KnownAnswer, String> {
+    let mut input = None;
+    let mut day = None;
+    let mut part = None;
+    let mut answer = None;
+
+    for entry in &table.entries {
+        match entry.key.as_str() {
+            "input" => input = Some(entry.string(SOURCE)?.to_string()),
+            "answer" => answer = Some(entry.string(SOURCE)?.to_string()),
+            "day" => match entry.integer(SOURCE)? {
+                n if n >= 1 && n <= i64::from(LAST_DAY) => day = Some(n as u32),
+                _ => Err(entry.error(SOURCE, "a day number"))?,
+            },
+            "part" => match entry.integer(SOURCE)? {
+                1 => part = Some(Part::One),
+                2 => part = Some(Part::Two),
+                _ => Err(entry.error(SOURCE, "1 or 2"))?,
+            },
+            _ => Err(format!(
+                "[[answer]] on line {} has unknown key '{}'",
+                table.line, entry.key
+            ))?,
+        }
+    }
+
+    let missing = |key| format!("[[answer]] on line {} is missing '{}'", table.line, key);
+    Ok(KnownAnswer {
+        input: input.ok_or_else(|| missing("input"))?,
+        day: day.ok_or_else(|| missing("day"))?,
+        part: part.ok_or_else(|| missing("part"))?,
+        answer: answer.ok_or_else(|| missing("answer"))?,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    // solved, but nothing recorded yet
+    New,
+    Error(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { .. } => write!(f, "FAIL"),
+            Status::New => write!(f, "new"),
+            Status::Error(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    pub input: String,
+    pub day: u32,
+    pub part: Part,
+    // None when the solver or input failed
+    pub answer: Option<String>,
+    pub status: Status,
+}
+
+// Run every solved day against every input it has answers for, plus its
+// default day_N_input.txt if that exists
+pub fn check(answers: &Answers, inputs_dir: &Path) -> Vec<CheckResult> {
+    let mut results = vec![];
+
+    for day in 1..=LAST_DAY {
+        let solver = match crate::solver(day) {
+            Some(solver) => solver,
+            None => continue,
+        };
+
+        let mut inputs: Vec<String> = answers
+            .answers
+            .iter()
+            .filter(|a| a.day == day)
+            .map(|a| a.input.clone())
+            .collect();
+        let default = input::day_path(Path::new(""), day);
+        let default = default.to_string_lossy().to_string();
+        if inputs_dir.join(&default).exists() {
+            inputs.push(default);
+        }
+        inputs.sort();
+        inputs.dedup();
+
+        for name in inputs {
+            let source = InputSource::Path(inputs_dir.join(&name));
+            let parsed = input::load(&source, inputs_dir).and_then(|input| solver.parse(&input));
+
+            for &part in &Part::BOTH {
+                let known = answers.get(&name, day, part);
+                let answer = match &parsed {
+                    Ok(parsed) => solver.solve(&**parsed, part),
+                    Err(e) => Err(e.to_string().into()),
+                };
+
+                let (answer, status) = match (answer, known) {
+                    (Err(e), _) => (None, Status::Error(e.to_string())),
+                    (Ok(answer), None) => (Some(answer), Status::New),
+                    (Ok(answer), Some(known)) if answer == known.answer => {
+                        (Some(answer), Status::Pass)
+                    }
+                    (Ok(answer), Some(known)) => (
+                        Some(answer),
+                        Status::Fail {
+                            expected: known.answer.clone(),
+                        },
+                    ),
+                };
+
+                results.push(CheckResult {
+                    input: name.clone(),
+                    day,
+                    part,
+                    answer,
+                    status,
+                });
+            }
+        }
+    }
+
+    results
+}
+
+// Record the answers from `New` results
+pub fn record(answers: &mut Answers, results: &[CheckResult]) -> usize {
+    let mut recorded = 0;
+    for result in results {
+        if let (Status::New, Some(answer)) = (&result.status, &result.answer) {
+            answers.answers.push(KnownAnswer {
+                input: result.input.clone(),
+                day: result.day,
+                part: result.part,
+                answer: answer.clone(),
+            });
+            recorded += 1;
+        }
+    }
+
+    answers
+        .answers
+        .sort_by(|l, r| (l.day, &l.input, l.part).cmp(&(r.day, &r.input, r.part)));
+
+    recorded
+}
+
+pub fn default_path() -> PathBuf {
+    PathBuf::from(DEFAULT_ANSWERS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers {
+            answers: vec![KnownAnswer {
+                input: "alice/day_7.txt".to_string(),
+                day: 7,
+                part: Part::One,
+                answer: "C\"AB".to_string(),
+            }],
+        };
+
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn repo_answers_parse() {
+        let answers = Answers::parse(include_str!("../answers.toml")).unwrap();
+        let day_4 = answers.get("day_4_input.txt", 4, Part::Two).unwrap();
+        assert_eq!(day_4.answer, "22687");
+    }
+
+    #[test]
+    fn missing_key() {
+        let error = Answers::parse("[[answer]]\nday = 1\npart = 1\nanswer = \"3\"").unwrap_err();
+        assert!(error.to_string().contains("missing 'input'"));
+    }
+
+    #[test]
+    fn bad_value() {
+        let error = Answers::parse("[[answer]]\nday = 1\npart = 3").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("line 3, column 8: expected 1 or 2"));
+    }
+}
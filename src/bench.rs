@@ -0,0 +1,317 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::input;
+use crate::{Part, Solver, StdResult};
+
+pub const DEFAULT_BASELINE: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/target/bench_baseline.txt");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [
+        Phase::Parse,
+        Phase::Solve(Part::One),
+        Phase::Solve(Part::Two),
+    ];
+
+    pub fn parse(s: &str) -> Option<Phase> {
+        Phase::ALL
+            .iter()
+            .cloned()
+            .find(|phase| phase.to_string() == s)
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part.number()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    // `samples` must not be empty
+    pub fn from_samples(samples: &[Duration]) -> Timings {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        Timings {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchResult {
+    pub day: u32,
+    pub iterations: usize,
+    pub input_checksum: String,
+    pub phases: Vec<(Phase, Timings)>,
+}
+
+// Parse and solve both parts `iterations` times, timing each phase separately
+pub fn bench(
+    day: u32,
+    solver: &dyn Solver,
+    input: &str,
+    iterations: usize,
+) -> StdResult<BenchResult> {
+    if iterations == 0 {
+        Err("need at least one iteration")?
+    }
+
+    let mut samples: Vec<Vec<Duration>> = vec![vec![]; Phase::ALL.len()];
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = solver.parse(input)?;
+        samples[0].push(start.elapsed());
+
+        for (i, &part) in Part::BOTH.iter().enumerate() {
+            let start = Instant::now();
+            solver.solve(&*parsed, part)?;
+            samples[i + 1].push(start.elapsed());
+        }
+    }
+
+    let phases = Phase::ALL
+        .iter()
+        .zip(samples.iter())
+        .map(|(&phase, samples)| (phase, Timings::from_samples(samples)))
+        .collect();
+
+    Ok(BenchResult {
+        day,
+        iterations,
+        input_checksum: input::checksum(input),
+        phases,
+    })
+}
+
+// Median timings from an earlier run, one line per day and phase:
+// "<day> <phase> <input checksum> <median nanoseconds>"
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    pub entries: Vec<BaselineEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BaselineEntry {
+    pub day: u32,
+    pub phase: Phase,
+    pub input_checksum: String,
+    pub median: Duration,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> StdResult<Baseline> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read '{}': {}", path.display(), e))?;
+        Baseline::parse(&text)
+            .map_err(|e| format!("bad baseline '{}': {}", path.display(), e).into())
+    }
+
+    pub fn parse(text: &str) -> StdResult<Baseline> {
+        let mut entries = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let entry = match fields.as_slice() {
+                [day, phase, checksum, nanos] => {
+                    let day = day.parse().ok();
+                    let phase = Phase::parse(phase);
+                    let nanos = nanos.parse().ok();
+                    match (day, phase, nanos) {
+                        (Some(day), Some(phase), Some(nanos)) => Some(BaselineEntry {
+                            day,
+                            phase,
+                            input_checksum: checksum.to_string(),
+                            median: Duration::from_nanos(nanos),
+                        }),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            entries.push(entry.ok_or_else(|| {
+                format!(
+                    "line {}: expected '<day> <phase> <checksum> <nanoseconds>'",
+                    i + 1
+                )
+            })?);
+        }
+
+        Ok(Baseline { entries })
+    }
+
+    pub fn get(&self, day: u32, phase: Phase, input_checksum: &str) -> Option<&BaselineEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.phase == phase && e.input_checksum == input_checksum)
+    }
+
+    // Replace any entries for the same day and input with this result
+    pub fn update(&mut self, result: &BenchResult) {
+        self.entries
+            .retain(|e| !(e.day == result.day && e.input_checksum == result.input_checksum));
+        for &(phase, timings) in &result.phases {
+            self.entries.push(BaselineEntry {
+                day: result.day,
+                phase,
+                input_checksum: result.input_checksum.clone(),
+                median: timings.median,
+            });
+        }
+        self.entries.sort_by_key(|e| (e.day, e.phase));
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text =
+            String::from("# day phase input-checksum median-ns, written by `aoc bench --save`\n");
+        for e in &self.entries {
+            text.push_str(&format!(
+                "{} {} {} {}\n",
+                e.day,
+                e.phase,
+                e.input_checksum,
+                e.median.as_nanos()
+            ));
+        }
+
+        text
+    }
+
+    pub fn save(&self, path: &Path) -> StdResult<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("couldn't create '{}': {}", dir.display(), e))?;
+        }
+        fs::write(path, self.to_text())
+            .map_err(|e| format!("couldn't write '{}': {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    // median change relative to the baseline, 0.1 = 10% slower
+    pub change: f64,
+    pub regressed: bool,
+}
+
+// Compare a phase's median against the baseline for the same day and input
+pub fn compare(
+    result: &BenchResult,
+    phase: Phase,
+    baseline: &Baseline,
+    threshold: f64,
+) -> Option<Comparison> {
+    let (_, timings) = result.phases.iter().find(|(p, _)| *p == phase)?;
+    let entry = baseline.get(result.day, phase, &result.input_checksum)?;
+
+    let base = entry.median.as_secs_f64();
+    let change = if base > 0.0 {
+        (timings.median.as_secs_f64() - base) / base
+    } else {
+        0.0
+    };
+
+    Some(Comparison {
+        baseline: entry.median,
+        change,
+        regressed: change > threshold,
+    })
+}
+
+pub fn default_baseline() -> PathBuf {
+    PathBuf::from(DEFAULT_BASELINE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn timings() {
+        let timings = Timings::from_samples(&[ms(5), ms(1), ms(3), ms(9)]);
+        assert_eq!(timings.min, ms(1));
+        assert_eq!(timings.median, ms(4));
+        assert_eq!(timings.max, ms(9));
+    }
+
+    #[test]
+    fn bench_phases() {
+        let solver = crate::solver(8).unwrap();
+        let result = bench(8, &*solver, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", 3).unwrap();
+        let phases: Vec<Phase> = result.phases.iter().map(|(p, _)| *p).collect();
+        assert_eq!(phases, Phase::ALL.to_vec());
+        assert!(bench(8, &*solver, "1 1 1", 0).is_err());
+    }
+
+    #[test]
+    fn regressions() {
+        let mut result = BenchResult {
+            day: 5,
+            iterations: 1,
+            input_checksum: "abc".to_string(),
+            phases: vec![(Phase::Parse, Timings::from_samples(&[ms(10)]))],
+        };
+
+        let mut baseline = Baseline::default();
+        baseline.update(&result);
+        let baseline = Baseline::parse(&baseline.to_text()).unwrap();
+
+        result.phases = vec![(Phase::Parse, Timings::from_samples(&[ms(12)]))];
+        let comparison = compare(&result, Phase::Parse, &baseline, 0.1).unwrap();
+        assert!(comparison.regressed);
+        assert!((comparison.change - 0.2).abs() < 1e-9);
+
+        assert!(
+            !compare(&result, Phase::Parse, &baseline, 0.25)
+                .unwrap()
+                .regressed
+        );
+
+        result.input_checksum = "other".to_string();
+        assert!(compare(&result, Phase::Parse, &baseline, 0.1).is_none());
+    }
+}
@@ -0,0 +1,352 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+// Just enough arbitrary precision integer for day 1's big mode: parsing,
+// printing, comparing, + -, abs and euclidean division and remainder. Slow
+// next to i64, but it can't overflow.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    // zero is never negative
+    negative: bool,
+    // little endian base 2^32 digits, with no trailing zeros
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            magnitude: self.magnitude.clone(),
+        }
+    }
+
+    // Rounds towards negative infinity for a positive `divisor`, like
+    // i64::div_euclid. Panics if `divisor` is zero.
+    pub fn div_euclid(&self, divisor: &BigInt) -> BigInt {
+        let (quotient, remainder) = self.div_rem(divisor);
+        if !remainder.is_negative() {
+            quotient
+        } else if divisor.is_negative() {
+            &quotient + &BigInt::from(1)
+        } else {
+            &quotient - &BigInt::from(1)
+        }
+    }
+
+    // Always in 0..|divisor|. Panics if `divisor` is zero.
+    pub fn rem_euclid(&self, divisor: &BigInt) -> BigInt {
+        let (_, remainder) = self.div_rem(divisor);
+        if remainder.is_negative() {
+            &remainder + &divisor.abs()
+        } else {
+            remainder
+        }
+    }
+
+    // Truncating division, like / and % on i64
+    fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "division by zero");
+
+        let (quotient, remainder) = div_rem_magnitudes(&self.magnitude, &divisor.magnitude);
+        (
+            BigInt::new(self.negative != divisor.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        )
+    }
+
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> BigInt {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> BigInt {
+        let abs = n.unsigned_abs();
+        BigInt::new(n < 0, vec![abs as u32, (abs >> 32) as u32])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid integer")
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    // Digits with an optional sign
+    fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+
+        let mut magnitude = vec![];
+        for chunk in digits.as_bytes().chunks(9) {
+            let value = chunk
+                .iter()
+                .fold(0, |value, b| value * 10 + u64::from(b - b'0'));
+            mul_add_small(&mut magnitude, 10u64.pow(chunk.len() as u32), value);
+        }
+
+        Ok(BigInt::new(negative, magnitude))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // base 10^9 chunks, least significant first
+        let mut chunks = vec![];
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            chunks.push(div_small(&mut magnitude, 1_000_000_000));
+        }
+
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        text.push_str(&chunks.pop().expect("non-zero").to_string());
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:09}", chunk));
+        }
+
+        write!(f, "{}", text)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            );
+        }
+
+        // opposite signs, so the bigger magnitude decides the sign
+        match cmp_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::new(
+                other.negative,
+                sub_magnitudes(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::new(
+                self.negative,
+                sub_magnitudes(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+fn cmp_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let total = u64::from(*a.get(i).unwrap_or(&0)) + u64::from(*b.get(i).unwrap_or(&0)) + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+
+    sum
+}
+
+// `a` must be at least `b`
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut total = i64::from(digit) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        borrow = if total < 0 {
+            total += 1 << 32;
+            1
+        } else {
+            0
+        };
+        difference.push(total as u32);
+    }
+
+    difference
+}
+
+fn mul_add_small(magnitude: &mut Vec<u32>, factor: u64, addend: u64) {
+    let mut carry = addend;
+    for digit in magnitude.iter_mut() {
+        let total = u64::from(*digit) * factor + carry;
+        *digit = total as u32;
+        carry = total >> 32;
+    }
+    while carry > 0 {
+        magnitude.push(carry as u32);
+        carry >>= 32;
+    }
+}
+
+// Divides in place, returning the remainder
+fn div_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for digit in magnitude.iter_mut().rev() {
+        let total = (remainder << 32) | u64::from(*digit);
+        *digit = (total / u64::from(divisor)) as u32;
+        remainder = total % u64::from(divisor);
+    }
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+
+    remainder as u32
+}
+
+// Shift and subtract, a bit at a time
+fn div_rem_magnitudes(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = vec![];
+    for bit in (0..a.len() * 32).rev() {
+        mul_add_small(
+            &mut remainder,
+            2,
+            u64::from((a[bit / 32] >> (bit % 32)) & 1),
+        );
+        if cmp_magnitudes(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitudes(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_print() {
+        for s in &[
+            "0",
+            "7",
+            "-7",
+            "4294967296",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(big(s).to_string(), *s);
+        }
+        assert_eq!(big("+0012").to_string(), "12");
+        assert_eq!(big("-0"), BigInt::zero());
+        assert!("".parse::<BigInt>().is_err());
+        assert!("1x".parse::<BigInt>().is_err());
+        assert_eq!(BigInt::from(i64::MIN).to_string(), i64::MIN.to_string());
+    }
+
+    #[test]
+    fn arithmetic_matches_i64() {
+        let values = [0i64, 1, -1, 7, -7, 1 << 40, -(1 << 40) + 3, 99_999_999_999];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(&x + &y, BigInt::from(a + b));
+                assert_eq!(&x - &y, BigInt::from(a - b));
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if b != 0 {
+                    assert_eq!(x.div_euclid(&y), BigInt::from(a.div_euclid(b)));
+                    assert_eq!(x.rem_euclid(&y), BigInt::from(a.rem_euclid(b)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn past_i64() {
+        let max = BigInt::from(i64::MAX);
+        assert_eq!((&max + &BigInt::from(1)).to_string(), "9223372036854775808");
+        let past = &(&max + &max) + &BigInt::from(3);
+        assert_eq!(past.to_string(), "18446744073709551617");
+        assert_eq!(past.div_euclid(&max), BigInt::from(2));
+        assert_eq!(past.rem_euclid(&max), BigInt::from(3));
+        assert_eq!((-&past).div_euclid(&max), BigInt::from(-3));
+    }
+}
@@ -0,0 +1,989 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use advent::answers::{self, Answers, Status};
+use advent::bench::{self, Baseline, BenchResult};
+use advent::day_3::{self, render, OverlapGraph};
+use advent::generate;
+use advent::input::{self, InputSource};
+use advent::params::{self, Config, Params};
+use advent::report::{self, PartReport};
+use advent::{Part, StdResult, LAST_DAY};
+
+const USAGE: &str =
+    "usage: aoc run <day> [--part 1|2] [--input PATH|-] [--inputs-dir DIR] [--format text|json]
+               [--config PATH] [--param KEY=VALUE]...
+       aoc run --all [--inputs-dir DIR] [--format text|json] [--config PATH]
+       aoc check [--answers PATH] [--inputs-dir DIR] [--record]
+       aoc bench <day> [--iterations N] [--input PATH|-] [--inputs-dir DIR]
+                 [--baseline PATH] [--save] [--threshold PERCENT]
+                 [--config PATH] [--param KEY=VALUE]...
+       aoc generate <day> [--seed N] [--size X] [--output PATH]
+       aoc generate --all --output-dir DIR [--seed N] [--size X]
+       aoc render 3 [--input PATH|-] [--inputs-dir DIR]
+                  [--format ascii|ppm|png|edges] [--output PATH]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    if let Err(e) = command.execute() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    // one JSON object per line, per part
+    Json,
+}
+
+impl Format {
+    fn parse(s: &str) -> StdResult<Format> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("format must be text or json, got '{}'", s))?,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Picture {
+    Ascii,
+    Ppm,
+    Png,
+    // the overlap graph, as "id id area" lines
+    Edges,
+}
+
+impl Picture {
+    fn parse(s: &str) -> StdResult<Picture> {
+        match s {
+            "ascii" => Ok(Picture::Ascii),
+            "ppm" => Ok(Picture::Ppm),
+            "png" => Ok(Picture::Png),
+            "edges" => Ok(Picture::Edges),
+            _ => Err(format!(
+                "format must be ascii, ppm, png or edges, got '{}'",
+                s
+            ))?,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        day: u32,
+        part: Option<Part>,
+        input: InputSource,
+        inputs_dir: Option<PathBuf>,
+        format: Format,
+        config: Option<PathBuf>,
+        // KEY=VALUE overrides of the config
+        params: Vec<String>,
+    },
+    RunAll {
+        inputs_dir: Option<PathBuf>,
+        format: Format,
+        config: Option<PathBuf>,
+    },
+    Check {
+        answers: Option<PathBuf>,
+        inputs_dir: Option<PathBuf>,
+        record: bool,
+    },
+    Bench {
+        day: u32,
+        iterations: usize,
+        input: InputSource,
+        inputs_dir: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        save: bool,
+        // percent slower than the baseline median that counts as a regression
+        threshold: f64,
+        config: Option<PathBuf>,
+        params: Vec<String>,
+    },
+    Generate {
+        day: u32,
+        seed: u64,
+        // multiple of a real input's size
+        size: f64,
+        // stdout if unset
+        output: Option<PathBuf>,
+    },
+    // day_N_input.txt for every solved day, ready for --inputs-dir
+    GenerateAll {
+        seed: u64,
+        size: f64,
+        output_dir: PathBuf,
+    },
+    // only day 3's fabric can be drawn so far
+    Render {
+        day: u32,
+        input: InputSource,
+        inputs_dir: Option<PathBuf>,
+        format: Picture,
+        // stdout if unset, ascii only
+        output: Option<PathBuf>,
+    },
+}
+
+fn parse_args(args: &[String]) -> StdResult<Command> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+    match command.as_str() {
+        "run" => parse_run(rest),
+        "check" => parse_check(rest),
+        "bench" => parse_bench(rest),
+        "generate" => parse_generate(rest),
+        "render" => parse_render(rest),
+        _ => Err(format!("unknown command '{}'", command))?,
+    }
+}
+
+fn parse_check(args: &[String]) -> StdResult<Command> {
+    let mut answers = None;
+    let mut inputs_dir = None;
+    let mut record = false;
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let value = rest.next().ok_or("--answers needs a value")?;
+                answers = Some(PathBuf::from(value));
+            }
+            "--inputs-dir" => {
+                let value = rest.next().ok_or("--inputs-dir needs a value")?;
+                inputs_dir = Some(PathBuf::from(value));
+            }
+            "--record" => record = true,
+            other => Err(format!("unknown option '{}'", other))?,
+        }
+    }
+
+    Ok(Command::Check {
+        answers,
+        inputs_dir,
+        record,
+    })
+}
+
+fn parse_bench(args: &[String]) -> StdResult<Command> {
+    let mut day = None;
+    let mut iterations = 10;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut baseline = None;
+    let mut save = false;
+    let mut threshold = 10.0;
+    let mut config = None;
+    let mut params = vec![];
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--iterations" => {
+                let value = rest.next().ok_or("--iterations needs a value")?;
+                iterations = match value.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => Err(format!(
+                        "iterations must be a positive number, got '{}'",
+                        value
+                    ))?,
+                };
+            }
+            "--input" => {
+                let value = rest.next().ok_or("--input needs a value")?;
+                input = Some(InputSource::from_arg(value));
+            }
+            "--inputs-dir" => {
+                let value = rest.next().ok_or("--inputs-dir needs a value")?;
+                inputs_dir = Some(PathBuf::from(value));
+            }
+            "--baseline" => {
+                let value = rest.next().ok_or("--baseline needs a value")?;
+                baseline = Some(PathBuf::from(value));
+            }
+            "--save" => save = true,
+            "--threshold" => {
+                let value = rest.next().ok_or("--threshold needs a value")?;
+                threshold = match value.parse::<f64>() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => Err(format!("threshold must be a percentage, got '{}'", value))?,
+                };
+            }
+            "--config" => {
+                let value = rest.next().ok_or("--config needs a value")?;
+                config = Some(PathBuf::from(value));
+            }
+            "--param" => {
+                let value = rest.next().ok_or("--param needs a value")?;
+                params.push(value.to_string());
+            }
+            flag if flag.starts_with("--") => Err(format!("unknown option '{}'", flag))?,
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    let day = day.ok_or("missing day")?;
+    Ok(Command::Bench {
+        day,
+        iterations,
+        input: input.unwrap_or(InputSource::Day(day)),
+        inputs_dir,
+        baseline,
+        save,
+        threshold,
+        config,
+        params,
+    })
+}
+
+fn parse_generate(args: &[String]) -> StdResult<Command> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = 1.0;
+    let mut output = None;
+    let mut output_dir = None;
+    let mut all = false;
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = rest.next().ok_or("--seed needs a value")?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("seed must be a number, got '{}'", value))?;
+            }
+            "--size" => {
+                let value = rest.next().ok_or("--size needs a value")?;
+                size = match value.parse::<f64>() {
+                    Ok(s) if s > 0.0 && s.is_finite() => s,
+                    _ => Err(format!("size must be a positive number, got '{}'", value))?,
+                };
+            }
+            "--output" => {
+                let value = rest.next().ok_or("--output needs a value")?;
+                output = Some(PathBuf::from(value));
+            }
+            "--output-dir" => {
+                let value = rest.next().ok_or("--output-dir needs a value")?;
+                output_dir = Some(PathBuf::from(value));
+            }
+            "--all" => all = true,
+            flag if flag.starts_with("--") => Err(format!("unknown option '{}'", flag))?,
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    match (all, day) {
+        (true, None) if output.is_none() => Ok(Command::GenerateAll {
+            seed,
+            size,
+            output_dir: output_dir.ok_or("--all needs --output-dir")?,
+        }),
+        (true, _) => Err("--all can't be combined with a day or --output")?,
+        (false, Some(day)) if output_dir.is_none() => Ok(Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        }),
+        (false, Some(_)) => Err("--output-dir only goes with --all, use --output")?,
+        (false, None) => Err("missing day")?,
+    }
+}
+
+fn parse_render(args: &[String]) -> StdResult<Command> {
+    let mut day = None;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut format = Picture::Ascii;
+    let mut output = None;
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--input" => {
+                let value = rest.next().ok_or("--input needs a value")?;
+                input = Some(InputSource::from_arg(value));
+            }
+            "--inputs-dir" => {
+                let value = rest.next().ok_or("--inputs-dir needs a value")?;
+                inputs_dir = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                let value = rest.next().ok_or("--format needs a value")?;
+                format = Picture::parse(value)?;
+            }
+            "--output" => {
+                let value = rest.next().ok_or("--output needs a value")?;
+                output = Some(PathBuf::from(value));
+            }
+            flag if flag.starts_with("--") => Err(format!("unknown option '{}'", flag))?,
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    let day = day.ok_or("missing day")?;
+    if day != 3 {
+        Err(format!(
+            "day {} has nothing to render, only day 3 does",
+            day
+        ))?
+    }
+    if (format == Picture::Ppm || format == Picture::Png) && output.is_none() {
+        Err("ppm and png need --output")?
+    }
+
+    Ok(Command::Render {
+        day,
+        input: input.unwrap_or(InputSource::Day(day)),
+        inputs_dir,
+        format,
+        output,
+    })
+}
+
+fn parse_day(value: &str) -> StdResult<u32> {
+    let day: u32 = value
+        .parse()
+        .map_err(|_| format!("day must be a number, got '{}'", value))?;
+    if day == 0 || day > LAST_DAY {
+        Err(format!("day must be between 1 and {}", LAST_DAY))?
+    }
+
+    Ok(day)
+}
+
+fn parse_run(args: &[String]) -> StdResult<Command> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut format = Format::Text;
+    let mut config = None;
+    let mut params = vec![];
+    let mut all = false;
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--part" => {
+                let value = rest.next().ok_or("--part needs a value")?;
+                part = Some(Part::parse(value)?);
+            }
+            "--input" => {
+                let value = rest.next().ok_or("--input needs a value")?;
+                input = Some(InputSource::from_arg(value));
+            }
+            "--inputs-dir" => {
+                let value = rest.next().ok_or("--inputs-dir needs a value")?;
+                inputs_dir = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                let value = rest.next().ok_or("--format needs a value")?;
+                format = Format::parse(value)?;
+            }
+            "--config" => {
+                let value = rest.next().ok_or("--config needs a value")?;
+                config = Some(PathBuf::from(value));
+            }
+            "--param" => {
+                let value = rest.next().ok_or("--param needs a value")?;
+                params.push(value.to_string());
+            }
+            flag if flag.starts_with("--") => Err(format!("unknown option '{}'", flag))?,
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    match (all, day) {
+        (true, None) if part.is_none() && input.is_none() && params.is_empty() => {
+            Ok(Command::RunAll {
+                inputs_dir,
+                format,
+                config,
+            })
+        }
+        (true, _) => Err("--all can't be combined with a day, --part, --input or --param")?,
+        (false, Some(day)) => Ok(Command::Run {
+            day,
+            part,
+            input: input.unwrap_or(InputSource::Day(day)),
+            inputs_dir,
+            format,
+            config,
+            params,
+        }),
+        (false, None) => Err("missing day")?,
+    }
+}
+
+impl Command {
+    fn execute(&self) -> StdResult<()> {
+        match self {
+            Command::Run {
+                day,
+                part,
+                input,
+                inputs_dir,
+                format,
+                config,
+                params,
+            } => {
+                if advent::solver(*day).is_none() {
+                    Err(format!("day {} is not solved yet", day))?
+                }
+
+                let params = day_params(*day, config, params)?;
+                let solver = advent::configured_solver(*day, &params)?;
+                let dir = input::inputs_dir(inputs_dir.as_deref());
+                let parts = match part {
+                    Some(part) => vec![*part],
+                    None => Part::BOTH.to_vec(),
+                };
+
+                let reports = match input::load(input, &dir) {
+                    Ok(input) => report::run(*day, &*solver, &input, &parts),
+                    Err(e) if *format == Format::Json => {
+                        report::missing_input(*day, &*solver, &parts, &e.to_string())
+                    }
+                    Err(e) => Err(e)?,
+                };
+
+                print_reports(&reports, *format)
+            }
+            Command::RunAll {
+                inputs_dir,
+                format,
+                config,
+            } => {
+                let dir = input::inputs_dir(inputs_dir.as_deref());
+                let config = load_config(config)?;
+                let mut missing = vec![];
+                let mut missing_inputs = vec![];
+                for day in 1..=LAST_DAY {
+                    if advent::solver(day).is_none() {
+                        missing.push(day.to_string());
+                        continue;
+                    }
+
+                    if !input::day_path(&dir, day).exists() {
+                        missing_inputs.push(day.to_string());
+                        continue;
+                    }
+
+                    let solver = advent::configured_solver(day, &config.params(day))?;
+                    let input = input::load(&InputSource::Day(day), &dir)?;
+                    print_reports(&report::run(day, &*solver, &input, &Part::BOTH), *format)?;
+                    if *format == Format::Text {
+                        println!();
+                    }
+                }
+
+                // keep stdout to one object per line in json mode
+                let summary = |line: String| match format {
+                    Format::Text => println!("{}", line),
+                    Format::Json => eprintln!("{}", line),
+                };
+                if !missing.is_empty() {
+                    summary(format!("Missing days: {}", missing.join(", ")));
+                }
+                if !missing_inputs.is_empty() {
+                    summary(format!(
+                        "Missing inputs in '{}': {}",
+                        dir.display(),
+                        missing_inputs.join(", ")
+                    ));
+                }
+
+                Ok(())
+            }
+            Command::Check {
+                answers,
+                inputs_dir,
+                record,
+            } => {
+                let path = answers.clone().unwrap_or_else(answers::default_path);
+                let dir = input::inputs_dir(inputs_dir.as_deref());
+                let mut known = Answers::load(&path)?;
+
+                let results = answers::check(&known, &dir);
+                print_check(&results);
+
+                if *record {
+                    let recorded = answers::record(&mut known, &results);
+                    known.save(&path)?;
+                    println!("Recorded {} new answers in '{}'", recorded, path.display());
+                }
+
+                let failures = results
+                    .iter()
+                    .filter(|r| matches!(r.status, Status::Fail { .. } | Status::Error(_)))
+                    .count();
+                if failures > 0 {
+                    Err(format!("{} answers failed the check", failures))?
+                }
+
+                Ok(())
+            }
+            Command::Bench {
+                day,
+                iterations,
+                input,
+                inputs_dir,
+                baseline,
+                save,
+                threshold,
+                config,
+                params,
+            } => {
+                let params = day_params(*day, config, params)?;
+                let solver = advent::configured_solver(*day, &params)?;
+                let dir = input::inputs_dir(inputs_dir.as_deref());
+                let input = input::load(input, &dir)?;
+                let path = baseline.clone().unwrap_or_else(bench::default_baseline);
+                let mut baseline = Baseline::load(&path)?;
+
+                let result = bench::bench(*day, &*solver, &input, *iterations)?;
+                let regressions = print_bench(&result, &baseline, *threshold);
+
+                if *save {
+                    baseline.update(&result);
+                    baseline.save(&path)?;
+                    println!("Saved baseline to '{}'", path.display());
+                }
+
+                if regressions > 0 {
+                    Err(format!(
+                        "{} phases regressed more than {}% against the baseline",
+                        regressions, threshold
+                    ))?
+                }
+
+                Ok(())
+            }
+            Command::Generate {
+                day,
+                seed,
+                size,
+                output,
+            } => {
+                let input = generate::generate(*day, *seed, *size)?;
+                match output {
+                    Some(path) => write(path, &input),
+                    None => {
+                        print!("{}", input);
+                        Ok(())
+                    }
+                }
+            }
+            Command::GenerateAll {
+                seed,
+                size,
+                output_dir,
+            } => {
+                fs::create_dir_all(output_dir)
+                    .map_err(|e| format!("couldn't create '{}': {}", output_dir.display(), e))?;
+                for day in 1..=LAST_DAY {
+                    if advent::solver(day).is_none() {
+                        continue;
+                    }
+
+                    let path = input::day_path(output_dir, day);
+                    write(&path, &generate::generate(day, *seed, *size)?)?;
+                    println!("Wrote '{}'", path.display());
+                }
+
+                Ok(())
+            }
+            Command::Render {
+                day: _,
+                input,
+                inputs_dir,
+                format,
+                output,
+            } => {
+                let dir = input::inputs_dir(inputs_dir.as_deref());
+                let claims = day_3::parse_claims(&input::load(input, &dir)?)?;
+                let picture = picture(&claims, *format)?;
+                match output {
+                    Some(path) => write(path, &picture),
+                    None => {
+                        print!("{}", String::from_utf8_lossy(&picture));
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn picture(claims: &[day_3::Claim], format: Picture) -> StdResult<Vec<u8>> {
+    Ok(match format {
+        Picture::Ascii => render::ascii(claims)?.into_bytes(),
+        Picture::Ppm => render::heatmap(claims)?.to_ppm(),
+        Picture::Png => render::heatmap(claims)?.to_png(),
+        Picture::Edges => OverlapGraph::new(claims).edge_list(claims).into_bytes(),
+    })
+}
+
+fn write<C: AsRef<[u8]>>(path: &PathBuf, contents: C) -> StdResult<()> {
+    fs::write(path, contents).map_err(|e| format!("couldn't write '{}': {}", path.display(), e))?;
+    Ok(())
+}
+
+// The config file if one was given or $AOC_CONFIG names one, else no params
+fn load_config(path: &Option<PathBuf>) -> StdResult<Config> {
+    let path = path
+        .clone()
+        .or_else(|| env::var_os(params::CONFIG_VAR).map(PathBuf::from));
+    match path {
+        Some(path) => Config::load(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+// A day's params from the config, with --param overrides on top
+fn day_params(day: u32, config: &Option<PathBuf>, overrides: &[String]) -> StdResult<Params> {
+    let mut params = load_config(config)?.params(day);
+    for arg in overrides {
+        params.set_arg(arg)?;
+    }
+
+    Ok(params)
+}
+
+fn print_bench(result: &BenchResult, baseline: &Baseline, threshold: f64) -> usize {
+    println!("Day {}, {} iterations", result.day, result.iterations);
+    println!(
+        "{:<6} {:>12} {:>12} {:>12} {:>12} {:>8}",
+        "phase", "min", "median", "max", "baseline", "change"
+    );
+
+    let mut regressions = 0;
+    for &(phase, timings) in &result.phases {
+        let comparison = bench::compare(result, phase, baseline, threshold / 100.0);
+        let (base, change) = match comparison {
+            Some(c) => {
+                let flag = if c.regressed {
+                    regressions += 1;
+                    " REGRESSED"
+                } else {
+                    ""
+                };
+                (
+                    millis(c.baseline),
+                    format!("{:+.1}%{}", c.change * 100.0, flag),
+                )
+            }
+            None => ("-".to_string(), "-".to_string()),
+        };
+
+        println!(
+            "{:<6} {:>12} {:>12} {:>12} {:>12} {:>8}",
+            phase.to_string(),
+            millis(timings.min),
+            millis(timings.median),
+            millis(timings.max),
+            base,
+            change
+        );
+    }
+
+    regressions
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn print_check(results: &[answers::CheckResult]) {
+    let width = results
+        .iter()
+        .map(|r| r.input.len())
+        .max()
+        .unwrap_or(0)
+        .max("input".len());
+
+    println!(
+        "{:<4} {:<4} {:<width$} {:<6} answer",
+        "day",
+        "part",
+        "input",
+        "status",
+        width = width
+    );
+    for result in results {
+        let answer = match &result.status {
+            Status::Fail { expected } => format!(
+                "{} (expected {})",
+                result.answer.as_deref().unwrap_or(""),
+                expected
+            ),
+            Status::Error(e) => e.lines().next().unwrap_or("").to_string(),
+            _ => result.answer.clone().unwrap_or_default(),
+        };
+
+        println!(
+            "{:<4} {:<4} {:<width$} {:<6} {}",
+            result.day,
+            result.part.number(),
+            result.input,
+            result.status.to_string(),
+            answer,
+            width = width
+        );
+    }
+}
+
+fn print_reports(reports: &[PartReport], format: Format) -> StdResult<()> {
+    match format {
+        Format::Text => {
+            if let Some(report) = reports.first() {
+                println!("Day {}", report.day);
+                for warning in &report.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+            }
+            for report in reports {
+                match &report.answer {
+                    Ok(answer) => println!("{}", answer_line(report, answer)),
+                    Err(failure) => eprintln!("{}: error: {}", report.part, failure.message()),
+                }
+            }
+        }
+        Format::Json => {
+            for report in reports {
+                println!("{}", report.to_json());
+            }
+        }
+    }
+
+    let failed = reports.iter().filter(|r| r.answer.is_err()).count();
+    if failed > 0 {
+        Err(format!("{} of {} parts failed", failed, reports.len()))?
+    }
+
+    Ok(())
+}
+
+// eg "Part Two: 10 (pass: 1, index: 2)"
+fn answer_line(report: &PartReport, answer: &str) -> String {
+    let details: Vec<String> = report
+        .details
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+
+    match details.as_slice() {
+        [] => format!("{}: {}", report.part, answer),
+        details => format!("{}: {} ({})", report.part, answer, details.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn run_one_part() {
+        let command = parse_args(&args("run 7 --part 2 --input foo.txt")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                day: 7,
+                part: Some(Part::Two),
+                input: InputSource::Path(PathBuf::from("foo.txt")),
+                inputs_dir: None,
+                format: Format::Text,
+                config: None,
+                params: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn run_with_params() {
+        let command = parse_args(&args(
+            "run 7 --config aoc.toml --param workers=2 --param base_seconds=0",
+        ))
+        .unwrap();
+        match command {
+            Command::Run { config, params, .. } => {
+                assert_eq!(config, Some(PathBuf::from("aoc.toml")));
+                assert_eq!(params, vec!["workers=2", "base_seconds=0"]);
+            }
+            command => panic!("unexpected {:?}", command),
+        }
+        assert!(parse_args(&args("run --all --param workers=2")).is_err());
+
+        let params = day_params(7, &None, &["workers=2".to_string()]).unwrap();
+        assert_eq!(params.get::<usize>("workers").unwrap(), Some(2));
+        assert!(day_params(7, &None, &["workers".to_string()]).is_err());
+    }
+
+    #[test]
+    fn run_from_stdin() {
+        match parse_args(&args("run 1 --input -")).unwrap() {
+            Command::Run { input, .. } => assert_eq!(input, InputSource::Stdin),
+            command => panic!("unexpected {:?}", command),
+        }
+    }
+
+    #[test]
+    fn run_all() {
+        assert_eq!(
+            parse_args(&args("run --all --inputs-dir mine --format json")).unwrap(),
+            Command::RunAll {
+                inputs_dir: Some(PathBuf::from("mine")),
+                format: Format::Json,
+                config: None,
+            }
+        );
+        assert!(parse_args(&args("run --all 3")).is_err());
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(
+            parse_args(&args("check --record --answers a.toml")).unwrap(),
+            Command::Check {
+                answers: Some(PathBuf::from("a.toml")),
+                inputs_dir: None,
+                record: true,
+            }
+        );
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(
+            parse_args(&args("bench 5 --iterations 3 --save --threshold 25")).unwrap(),
+            Command::Bench {
+                day: 5,
+                iterations: 3,
+                input: InputSource::Day(5),
+                inputs_dir: None,
+                baseline: None,
+                save: true,
+                threshold: 25.0,
+                config: None,
+                params: vec![],
+            }
+        );
+        assert!(parse_args(&args("bench 5 --iterations 0")).is_err());
+        assert!(parse_args(&args("bench --save")).is_err());
+    }
+
+    #[test]
+    fn generate() {
+        assert_eq!(
+            parse_args(&args("generate 3 --seed 42 --size 100")).unwrap(),
+            Command::Generate {
+                day: 3,
+                seed: 42,
+                size: 100.0,
+                output: None,
+            }
+        );
+        assert_eq!(
+            parse_args(&args("generate --all --output-dir big --size 0.5")).unwrap(),
+            Command::GenerateAll {
+                seed: 0,
+                size: 0.5,
+                output_dir: PathBuf::from("big"),
+            }
+        );
+        assert!(parse_args(&args("generate --all")).is_err());
+        assert!(parse_args(&args("generate 3 --size -1")).is_err());
+        assert!(parse_args(&args("generate 3 --output-dir big")).is_err());
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(
+            parse_args(&args("render 3 --input - --format png --output fabric.png")).unwrap(),
+            Command::Render {
+                day: 3,
+                input: InputSource::Stdin,
+                inputs_dir: None,
+                format: Picture::Png,
+                output: Some(PathBuf::from("fabric.png")),
+            }
+        );
+        assert!(parse_args(&args("render 3")).is_ok());
+        assert!(parse_args(&args("render 3 --format ppm")).is_err());
+        assert!(parse_args(&args("render 3 --format svg --output f.svg")).is_err());
+        assert!(parse_args(&args("render 2")).is_err());
+        assert!(parse_args(&args("render 3 --format edges")).is_ok());
+
+        let claims = day_3::parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap();
+        assert_eq!(picture(&claims, Picture::Edges).unwrap(), b"1 2 4\n");
+    }
+
+    #[test]
+    fn every_part_printed() {
+        let solver = advent::solver(1).unwrap();
+        let mut reports = report::run(1, &*solver, "+1\n-1", &Part::BOTH);
+        // part one failing mustn't stop part two being printed
+        reports[0].answer = Err(report::Failure::Solve("overflow".to_string()));
+
+        for &format in &[Format::Text, Format::Json] {
+            let error = print_reports(&reports, format).unwrap_err();
+            assert_eq!(error.to_string(), "1 of 2 parts failed");
+        }
+        reports[0].answer = Ok("0".to_string());
+        assert!(print_reports(&reports, Format::Text).is_ok());
+    }
+
+    #[test]
+    fn repeat_position() {
+        let solver = advent::solver(1).unwrap();
+        let reports = report::run(1, &*solver, "+3\n+3\n+4\n-2\n-4", &Part::BOTH);
+        let line = |report: &PartReport| answer_line(report, report.answer.as_ref().unwrap());
+
+        assert_eq!(line(&reports[0]), "Part One: 4");
+        assert_eq!(
+            line(&reports[1]),
+            "Part Two: 10 (pass: 1, index: 1, first_pass: 0, first_index: 2)"
+        );
+        assert!(reports[1]
+            .to_json()
+            .to_string()
+            .contains(r#""details":{"pass":"1","index":"1","first_pass":"0","first_index":"2"}"#));
+
+        // a repeat of the starting 0 was never reached by a change
+        let reports = report::run(1, &*solver, "+1\n-1", &[Part::Two]);
+        assert_eq!(line(&reports[0]), "Part Two: 0 (pass: 0, index: 1)");
+    }
+
+    #[test]
+    fn bad_args() {
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run 26")).is_err());
+        assert!(parse_args(&args("run 3 --part 3")).is_err());
+        assert!(parse_args(&args("run 3 --format yaml")).is_err());
+        assert!(parse_args(&args("walk 3")).is_err());
+    }
+}
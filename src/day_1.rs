@@ -0,0 +1,473 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use crate::bigint::BigInt;
+use crate::error::{LineError, ParseError};
+use crate::params::Params;
+use crate::parser::Parser;
+use crate::{Part, Solution, Solver, StdResult};
+
+const OVERFLOW: &str = "the frequency overflows an i64, try --param precision=big";
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> StdResult<Vec<i64>> {
+        Ok(parse_changes(input)?)
+    }
+
+    fn part_one(&self, vals: &Vec<i64>) -> StdResult<i64> {
+        part_one(vals)
+    }
+
+    fn part_two(&self, vals: &Vec<i64>) -> StdResult<i64> {
+        Ok(part_two(vals)?.ok_or("no frequency is ever reached twice")?)
+    }
+
+    fn details(&self, vals: &Vec<i64>, part: Part) -> Vec<(&'static str, String)> {
+        repeat_details(vals, part)
+    }
+}
+
+// Day 1 in arbitrary precision, for change lists whose frequencies (or repeat
+// passes) don't fit in an i64
+pub struct BigDay1;
+
+impl Solution for BigDay1 {
+    type Input = Vec<BigInt>;
+    type PartOne = BigInt;
+    type PartTwo = BigInt;
+
+    fn parse(&self, input: &str) -> StdResult<Vec<BigInt>> {
+        Ok(parse_changes(input)?)
+    }
+
+    fn part_one(&self, vals: &Vec<BigInt>) -> StdResult<BigInt> {
+        part_one(vals)
+    }
+
+    fn part_two(&self, vals: &Vec<BigInt>) -> StdResult<BigInt> {
+        Ok(part_two(vals)?.ok_or("no frequency is ever reached twice")?)
+    }
+
+    fn details(&self, vals: &Vec<BigInt>, part: Part) -> Vec<(&'static str, String)> {
+        repeat_details(vals, part)
+    }
+}
+
+// Day1, or BigDay1 with "precision" set to "big"
+pub fn from_params(params: &Params) -> StdResult<Box<dyn Solver>> {
+    params.expect_only(&["precision"])?;
+
+    match params.get::<String>("precision")?.as_deref() {
+        None | Some("i64") => Ok(Box::new(Day1)),
+        Some("big") => Ok(Box::new(BigDay1)),
+        Some(other) => Err(format!(
+            "day 1 precision must be i64 or big, got '{}'",
+            other
+        ))?,
+    }
+}
+
+// The arithmetic the frequency search needs; i64's checked ops return None
+// on overflow, BigInt's never do
+pub trait Frequency: Clone + Debug + Ord + Hash + FromStr {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_abs(&self) -> Option<Self>;
+    fn checked_rem_euclid(&self, other: &Self) -> Option<Self>;
+    // `other` is positive
+    fn div_euclid(&self, other: &Self) -> Self;
+}
+
+impl Frequency for i64 {
+    fn zero() -> i64 {
+        0
+    }
+
+    fn one() -> i64 {
+        1
+    }
+
+    fn checked_add(&self, other: &i64) -> Option<i64> {
+        i64::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &i64) -> Option<i64> {
+        i64::checked_sub(*self, *other)
+    }
+
+    fn checked_abs(&self) -> Option<i64> {
+        i64::checked_abs(*self)
+    }
+
+    fn checked_rem_euclid(&self, other: &i64) -> Option<i64> {
+        i64::checked_rem_euclid(*self, *other)
+    }
+
+    fn div_euclid(&self, other: &i64) -> i64 {
+        i64::div_euclid(*self, *other)
+    }
+}
+
+impl Frequency for BigInt {
+    fn zero() -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one() -> BigInt {
+        BigInt::from(1)
+    }
+
+    fn checked_add(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self - other)
+    }
+
+    fn checked_abs(&self) -> Option<BigInt> {
+        Some(self.abs())
+    }
+
+    fn checked_rem_euclid(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self.rem_euclid(other))
+    }
+
+    fn div_euclid(&self, other: &BigInt) -> BigInt {
+        BigInt::div_euclid(self, other)
+    }
+}
+
+// Changes like "+1", "-2" or "3", one per line as in real inputs or comma
+// separated as in the puzzle's examples. Any whitespace, blank lines included,
+// can go between them.
+pub fn parse_changes<N: FromStr>(input: &str) -> Result<Vec<N>, ParseError> {
+    let located = |e: LineError| ParseError::at_offset(1, input, e.offset, &e.expected);
+
+    let mut p = Parser::new(input);
+    let mut changes = vec![];
+    p.skip_whitespace();
+    while !p.is_done() {
+        let token = p.offset();
+        let change = p.integer().map_err(located)?;
+        // "+1x" is a bad change, not +1 followed by a bad separator
+        if !(p.is_done()
+            || p.rest()
+                .starts_with(|c: char| c == ',' || c.is_whitespace()))
+        {
+            Err(located(LineError::new(token, "a number")))?
+        }
+        changes.push(change);
+
+        p.skip_whitespace();
+        if p.eat(",") {
+            p.skip_whitespace();
+            if p.is_done() {
+                Err(located(p.error("a number after ','")))?
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+pub fn part_one<N: Frequency>(vals: &[N]) -> StdResult<N> {
+    let mut freq = N::zero();
+    for change in vals {
+        freq = freq.checked_add(change).ok_or(OVERFLOW)?;
+    }
+
+    Ok(freq)
+}
+
+pub fn part_two<N: Frequency>(vals: &[N]) -> StdResult<Option<N>> {
+    Ok(first_repeat(vals)?.map(|repeat| repeat.frequency))
+}
+
+// Where part two's repeat happens: the pass and index of the change reaching
+// it, then of the change that first reached it (absent for the starting 0)
+fn repeat_details<N: Frequency + Display>(vals: &[N], part: Part) -> Vec<(&'static str, String)> {
+    let repeat = match (part, first_repeat(vals)) {
+        (Part::Two, Ok(Some(repeat))) => repeat,
+        _ => return vec![],
+    };
+
+    let mut details = vec![
+        ("pass", repeat.at.pass.to_string()),
+        ("index", repeat.at.index.to_string()),
+    ];
+    if let Some(first) = repeat.first {
+        details.push(("first_pass", first.pass.to_string()));
+        details.push(("first_index", first.index.to_string()));
+    }
+    details
+}
+
+// Which change of which pass over the list reached a frequency, both from 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position<N = i64> {
+    pub pass: N,
+    pub index: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repeat<N = i64> {
+    pub frequency: N,
+    pub at: Position<N>,
+    // None when the repeat is of the starting frequency, 0
+    pub first: Option<Position<N>>,
+}
+
+// The first frequency reached twice, or None if none ever is.
+//
+// Each pass reaches the first pass's frequencies shifted by the drift (the
+// sum of the changes), so two of them can only meet if they're congruent
+// modulo the drift. Within each class, the one drifting towards its nearest
+// neighbour gets there after (gap / |drift|) passes; the earliest of those
+// meetings is the answer.
+pub fn first_repeat<N: Frequency>(vals: &[N]) -> StdResult<Option<Repeat<N>>> {
+    // the frequency before each change of the first pass
+    let mut starts = Vec::with_capacity(vals.len());
+    let mut drift = N::zero();
+    for change in vals {
+        starts.push(drift.clone());
+        drift = drift.checked_add(change).ok_or(OVERFLOW)?;
+    }
+
+    // meetings are at the start of a (pass, index), ie before that change
+    let repeat = |frequency: &N, at: (N, usize), first: (N, usize)| Repeat {
+        frequency: frequency.clone(),
+        at: reached_by(vals.len(), at).expect("a repeat is after the start"),
+        first: reached_by(vals.len(), first),
+    };
+
+    let mut seen = HashMap::new();
+    for (i, start) in starts.iter().enumerate() {
+        if let Some(&first) = seen.get(start) {
+            return Ok(Some(repeat(start, (N::zero(), i), (N::zero(), first))));
+        }
+        seen.insert(start, i);
+    }
+    if drift == N::zero() {
+        // back to 0 at the end of the first pass
+        return Ok(starts
+            .first()
+            .map(|start| repeat(start, (N::one(), 0), (N::zero(), 0))));
+    }
+
+    let speed = drift.checked_abs().ok_or(OVERFLOW)?;
+    let mut classes: HashMap<N, Vec<(&N, usize)>> = HashMap::new();
+    for (i, start) in starts.iter().enumerate() {
+        let class = start.checked_rem_euclid(&drift).ok_or(OVERFLOW)?;
+        classes.entry(class).or_default().push((start, i));
+    }
+
+    let mut first: Option<((N, usize), Repeat<N>)> = None;
+    for class in classes.values_mut() {
+        class.sort_unstable();
+        for pair in class.windows(2) {
+            let ((low, low_index), (high, high_index)) = (pair[0], pair[1]);
+            let (mover, (target, target_index)) = if drift > N::zero() {
+                (low_index, (high, high_index))
+            } else {
+                (high_index, (low, low_index))
+            };
+
+            let gap = high.checked_sub(low).ok_or(OVERFLOW)?;
+            let at = (gap.div_euclid(&speed), mover);
+            if first.as_ref().is_none_or(|(earliest, _)| at < *earliest) {
+                let found = repeat(target, at.clone(), (N::zero(), target_index));
+                first = Some((at, found));
+            }
+        }
+    }
+
+    Ok(first.map(|(_, repeat)| repeat))
+}
+
+// The change that led to the start of `index` in `pass`, or None for the very
+// start
+fn reached_by<N: Frequency>(len: usize, (pass, index): (N, usize)) -> Option<Position<N>> {
+    if index > 0 {
+        Some(Position {
+            pass,
+            index: index - 1,
+        })
+    } else if pass == N::zero() {
+        None
+    } else {
+        Some(Position {
+            pass: pass.checked_sub(&N::one()).expect("pass is positive"),
+            index: len - 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // the old cycle-until-seen search, which only finishes if there's a
+    // repeat; the frequency and how many changes it took
+    fn simulate(vals: &[i64]) -> (i64, usize) {
+        let mut seen = HashSet::new();
+        seen.insert(0);
+
+        vals.iter()
+            .cycle()
+            .scan(0, |freq, &item| {
+                *freq += item;
+                Some(*freq)
+            })
+            .enumerate()
+            .find(|&(_, f)| !seen.insert(f))
+            .map(|(i, f)| (f, i + 1))
+            .unwrap()
+    }
+
+    fn two(vals: &[i64]) -> Option<i64> {
+        part_two(vals).unwrap()
+    }
+
+    #[test]
+    fn notations() {
+        let expected = vec![1, -2, 3, 1];
+        assert_eq!(parse_changes::<i64>("+1, -2, +3, +1").unwrap(), expected);
+        assert_eq!(
+            parse_changes::<i64>("+1\n-2\n\n+3\n+1\n").unwrap(),
+            expected
+        );
+        assert_eq!(parse_changes::<i64>("1 -2\t3,1").unwrap(), expected);
+        assert_eq!(parse_changes::<i64>("\n\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn bad_changes() {
+        let error = parse_changes::<i64>("+1, x, +3").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.expected, "a number");
+
+        let error = parse_changes::<i64>("+1\n\n+2y\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        assert!(parse_changes::<i64>("+1,, +2").is_err());
+        assert!(parse_changes::<i64>("+1, +2,").is_err());
+        assert!(parse_changes::<i64>("+ 1").is_err());
+    }
+
+    #[test]
+    fn example_one() {
+        assert_eq!(Some(0), two(&[1, -1]));
+    }
+
+    #[test]
+    fn example_two() {
+        assert_eq!(Some(10), two(&[3, 3, 4, -2, -4]));
+        assert_eq!(Some(5), two(&[-6, 3, 8, 5, -6]));
+        assert_eq!(Some(14), two(&[7, 7, -2, -7, -4]));
+    }
+
+    #[test]
+    fn repeat_positions() {
+        // 0 +3 3 +3 6 +4 10 -2 8 -4 4 | +3 7 +3 10
+        let repeat = first_repeat(&[3, 3, 4, -2, -4]).unwrap().unwrap();
+        assert_eq!(repeat.frequency, 10);
+        assert_eq!(repeat.at, Position { pass: 1, index: 1 });
+        assert_eq!(repeat.first, Some(Position { pass: 0, index: 2 }));
+
+        let repeat = first_repeat(&[1, -1]).unwrap().unwrap();
+        assert_eq!(repeat.at, Position { pass: 0, index: 1 });
+        assert_eq!(repeat.first, None);
+
+        // 0 -1 -1 -2 +1 -1
+        let repeat = first_repeat(&[-1, -1, 1]).unwrap().unwrap();
+        assert_eq!(repeat.frequency, -1);
+        assert_eq!(repeat.at, Position { pass: 0, index: 2 });
+        assert_eq!(repeat.first, Some(Position { pass: 0, index: 0 }));
+    }
+
+    #[test]
+    fn never_repeats() {
+        assert_eq!(None, two(&[1]));
+        assert_eq!(None, two(&[1, 2]));
+        assert_eq!(None, two(&[]));
+        assert!(Day1.part_two(&vec![-3, 1]).is_err());
+    }
+
+    #[test]
+    fn matches_simulation() {
+        for seed in 0..5 {
+            let input = crate::generate::generate(1, seed, 0.2).unwrap();
+            let vals: Vec<i64> = parse_changes(&input).unwrap();
+            let negated: Vec<i64> = vals.iter().map(|v| -v).collect();
+
+            for vals in &[vals.clone(), negated] {
+                let repeat = first_repeat(vals).unwrap().unwrap();
+                let steps = repeat.at.pass as usize * vals.len() + repeat.at.index + 1;
+                assert_eq!((repeat.frequency, steps), simulate(vals));
+            }
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let vals = [i64::MAX, 1, -1];
+        assert!(part_one(&vals).is_err());
+        assert!(part_two(&vals).is_err());
+        assert!(part_two(&[i64::MIN, -1]).is_err());
+
+        let big: Vec<BigInt> = vals.iter().map(|&v| BigInt::from(v)).collect();
+        assert_eq!(part_one(&big).unwrap().to_string(), "9223372036854775807");
+        // 0 MAX MAX+1 MAX | 2MAX ...
+        assert_eq!(part_two(&big).unwrap(), Some(BigInt::from(i64::MAX)));
+    }
+
+    #[test]
+    fn big_mode() {
+        let mut params = Params::new(1);
+        params.set("precision", "big");
+        let solver = from_params(&params).unwrap();
+        let input = solver
+            .parse("+1000000000000000000000000000000, -999999999999999999999999999999")
+            .unwrap();
+        assert_eq!(solver.solve(&*input, Part::One).unwrap(), "1");
+
+        // each pass ends 1 higher, so the end of pass 10^30 - 1 is back at 10^30
+        let big: Vec<BigInt> =
+            parse_changes("+1000000000000000000000000000000, -999999999999999999999999999999")
+                .unwrap();
+        let repeat = first_repeat(&big).unwrap().unwrap();
+        assert_eq!(
+            repeat.frequency.to_string(),
+            "1000000000000000000000000000000"
+        );
+        assert_eq!(repeat.at.pass.to_string(), "999999999999999999999999999999");
+        assert_eq!(repeat.at.index, 1);
+
+        params.set("precision", "i128");
+        assert!(from_params(&params).is_err());
+    }
+
+    #[test]
+    fn big_matches_i64() {
+        let input = crate::generate::generate(1, 7, 0.2).unwrap();
+        let vals: Vec<i64> = parse_changes(&input).unwrap();
+        let big: Vec<BigInt> = parse_changes(&input).unwrap();
+        let repeat = first_repeat(&vals).unwrap().unwrap();
+        let big_repeat = first_repeat(&big).unwrap().unwrap();
+        assert_eq!(big_repeat.frequency, BigInt::from(repeat.frequency));
+        assert_eq!(big_repeat.at.pass, BigInt::from(repeat.at.pass));
+        assert_eq!(big_repeat.at.index, repeat.at.index);
+    }
+}
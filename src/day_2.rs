@@ -0,0 +1,894 @@
+use std::collections::{HashMap, HashSet};
+use std::iter;
+use std::str::FromStr;
+
+use crate::error::parse_lines;
+use crate::grapheme::graphemes;
+use crate::params::Params;
+use crate::parser::whole;
+use crate::{Solution, StdResult};
+
+#[derive(Default)]
+pub struct Day2 {
+    pub checksum: Checksum,
+    pub matcher: Matcher,
+    // how far apart part two's IDs are; the matcher is only for Hamming
+    pub distance: Distance,
+}
+
+impl Day2 {
+    pub fn from_params(params: &Params) -> StdResult<Day2> {
+        params.expect_only(&["checksum_counts", "matcher", "distance"])?;
+        let default = Day2::default();
+
+        Ok(Day2 {
+            checksum: params.get("checksum_counts")?.unwrap_or(default.checksum),
+            matcher: params.get("matcher")?.unwrap_or(default.matcher),
+            distance: params.get("distance")?.unwrap_or(default.distance),
+        })
+    }
+}
+
+// How part two finds the pair of IDs one letter apart
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Matcher {
+    // hash every ID with each letter masked out, O(n·L) expected
+    #[default]
+    Masked,
+    // compare every ID with every earlier one, O(n²·L)
+    Pairwise,
+}
+
+impl FromStr for Matcher {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Matcher, String> {
+        match s {
+            "masked" => Ok(Matcher::Masked),
+            "pairwise" => Ok(Matcher::Pairwise),
+            _ => Err(format!("unknown matcher '{}'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Distance {
+    // differing positions, strictly between IDs of the same length
+    #[default]
+    Hamming,
+    // insertions, deletions and substitutions
+    Levenshtein,
+    // Levenshtein plus swapping adjacent letters
+    Damerau,
+}
+
+impl FromStr for Distance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Distance, String> {
+        match s {
+            "hamming" => Ok(Distance::Hamming),
+            "levenshtein" => Ok(Distance::Levenshtein),
+            "damerau" => Ok(Distance::Damerau),
+            _ => Err(format!("unknown distance '{}'", s)),
+        }
+    }
+}
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    fn parse(&self, input: &str) -> StdResult<Vec<String>> {
+        let ids = parse_lines(2, input, |line| {
+            let id = whole(line, |p| p.take_while1(|c| !c.is_whitespace(), "a box ID"))?;
+            Ok(id.to_string())
+        })?;
+
+        Ok(ids)
+    }
+
+    fn part_one(&self, ids: &Vec<String>) -> StdResult<usize> {
+        Ok(self.checksum.report(ids)?.product)
+    }
+
+    fn part_two(&self, ids: &Vec<String>) -> StdResult<String> {
+        part_two(ids, self.matcher, self.distance)
+    }
+}
+
+// Multiplies together how many IDs have some letter exactly `count` times,
+// for each count in the set. The puzzle's checksum is over {2, 3}.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    // ascending, no repeats
+    counts: Vec<usize>,
+}
+
+impl Default for Checksum {
+    fn default() -> Checksum {
+        Checksum::new(&[2, 3])
+    }
+}
+
+impl Checksum {
+    pub fn new(counts: &[usize]) -> Checksum {
+        counts
+            .iter()
+            .fold(Checksum { counts: vec![] }, |checksum, &count| {
+                checksum.with_count(count)
+            })
+    }
+
+    pub fn with_count(mut self, count: usize) -> Checksum {
+        if let Err(i) = self.counts.binary_search(&count) {
+            self.counts.insert(i, count);
+        }
+
+        self
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    // Letters are graphemes, so "e\u{301}" is one letter
+    pub fn report(&self, ids: &[String]) -> StdResult<ChecksumReport> {
+        let mut factors: Vec<Factor> = self
+            .counts
+            .iter()
+            .map(|&count| Factor { count, ids: vec![] })
+            .collect();
+
+        for (i, id) in ids.iter().enumerate() {
+            let counts = letter_counts(id);
+            for factor in &mut factors {
+                if counts.values().any(|&c| c == factor.count) {
+                    factor.ids.push(i);
+                }
+            }
+        }
+
+        let product = factors
+            .iter()
+            .try_fold(1usize, |product, factor| {
+                product.checked_mul(factor.value())
+            })
+            .ok_or("the checksum is too large")?;
+
+        Ok(ChecksumReport { factors, product })
+    }
+}
+
+// eg "2,3,4"
+impl FromStr for Checksum {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Checksum, String> {
+        let mut counts = vec![];
+        for count in s.split(',') {
+            match count.trim().parse() {
+                Ok(count) if count > 0 => counts.push(count),
+                _ => Err(format!("bad checksum count '{}'", count.trim()))?,
+            }
+        }
+
+        Ok(Checksum::new(&counts))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Factor {
+    pub count: usize,
+    // indexes of the IDs with a letter exactly `count` times
+    pub ids: Vec<usize>,
+}
+
+impl Factor {
+    pub fn value(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumReport {
+    // one per count, in ascending order
+    pub factors: Vec<Factor>,
+    pub product: usize,
+}
+
+fn letter_counts(id: &str) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for letter in graphemes(id) {
+        *counts.entry(letter).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+pub fn part_one(ids: &[String]) -> StdResult<usize> {
+    Ok(Checksum::default().report(ids)?.product)
+}
+
+// None for IDs of different lengths, which Hamming distance can't compare.
+// Letters are graphemes here too, as in the checksum.
+fn count_differences(left: &str, right: &str) -> Option<usize> {
+    let (left, right) = (graphemes(left), graphemes(right));
+    if left.len() != right.len() {
+        return None;
+    }
+
+    Some(left.iter().zip(&right).filter(|(l, r)| l != r).count())
+}
+
+fn correct_boxes(left: &str, right: &str) -> bool {
+    count_differences(left, right) == Some(1)
+}
+
+pub fn matching_boxes(ids: &[String], matcher: Matcher) -> Option<(String, String)> {
+    match matcher {
+        Matcher::Masked => masked_matching_boxes(ids),
+        Matcher::Pairwise => pairwise_matching_boxes(ids),
+    }
+}
+
+// Two IDs differ in exactly one letter iff they're the same with that letter
+// masked out, so each ID only needs looking up once per letter. Masks are
+// keyed by rolling hashes of the letters either side, making each lookup
+// O(1), and letters are only compared when the hashes agree.
+pub fn masked_matching_boxes(ids: &[String]) -> Option<(String, String)> {
+    // (length, masked position, hashes before and after it) to the first ID
+    // with that mask, and any later ones whose hashes only collided with it
+    let mut seen: HashMap<(usize, usize, u64, u64), &str> = HashMap::new();
+    let mut collided: HashMap<(usize, usize, u64, u64), Vec<&str>> = HashMap::new();
+    // an identical ID matches every mask but isn't a pair
+    let mut distinct = HashSet::new();
+    for line in ids {
+        if !distinct.insert(line) {
+            continue;
+        }
+
+        let letters = graphemes(line);
+        let (before, after) = rolling_hashes(&letters);
+        for p in 0..letters.len() {
+            let key = (letters.len(), p, before[p], after[p + 1]);
+            let first = match seen.get(&key) {
+                Some(&first) => first,
+                None => {
+                    seen.insert(key, line);
+                    continue;
+                }
+            };
+
+            let unmasked = |other: &&str| {
+                let other = graphemes(other);
+                other[..p] == letters[..p] && other[p + 1..] == letters[p + 1..]
+            };
+            let others = collided.entry(key).or_default();
+            if let Some(other) = iter::once(&first).chain(&*others).find(|o| unmasked(o)) {
+                return Some((other.to_string(), line.to_string()));
+            }
+            others.push(line);
+        }
+    }
+
+    None
+}
+
+// before[i] hashes the first i letters and after[i] the letters from i on
+fn rolling_hashes(letters: &[&str]) -> (Vec<u64>, Vec<u64>) {
+    const BASE: u64 = 0x9e37_79b9_7f4a_7c15;
+    // FNV-1a, so every letter gets a well mixed value however long it is
+    let hash = |letter: &str| {
+        letter.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    };
+
+    let mut before = vec![0u64; letters.len() + 1];
+    let mut after = vec![0u64; letters.len() + 1];
+    for (i, letter) in letters.iter().enumerate() {
+        before[i + 1] = before[i].wrapping_mul(BASE).wrapping_add(hash(letter));
+    }
+    for (i, letter) in letters.iter().enumerate().rev() {
+        after[i] = after[i + 1].wrapping_mul(BASE).wrapping_add(hash(letter));
+    }
+
+    (before, after)
+}
+
+pub fn pairwise_matching_boxes(ids: &[String]) -> Option<(String, String)> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    for line in ids {
+        for id in &seen {
+            if correct_boxes(id, line) {
+                return Some((id.to_string(), line.to_string()));
+            }
+        }
+
+        seen.insert(line);
+    }
+
+    None
+}
+
+// The first pair one edit apart, by the later ID's line
+pub fn edited_boxes(ids: &[String], distance: Distance) -> Option<(String, String)> {
+    near_pairs(ids, 1, distance)
+        .into_iter()
+        .filter(|pair| pair.distance == 1)
+        .min_by_key(|pair| (pair.right, pair.left))
+        .map(|pair| (ids[pair.left].clone(), ids[pair.right].clone()))
+}
+
+// Every ID must be as long as the first for Hamming distance to make sense
+fn check_lengths(ids: &[String]) -> StdResult<()> {
+    let first = match ids.first() {
+        Some(first) => first,
+        None => return Ok(()),
+    };
+
+    let first_len = graphemes(first).len();
+    for (i, id) in ids.iter().enumerate() {
+        let len = graphemes(id).len();
+        if len != first_len {
+            Err(format!(
+                "box ID on line {} is {} letters but line 1's is {}, \
+                 try --param distance=levenshtein",
+                i + 1,
+                len,
+                first_len
+            ))?
+        }
+    }
+
+    Ok(())
+}
+
+pub fn part_two(ids: &[String], matcher: Matcher, distance: Distance) -> StdResult<String> {
+    let boxes = match distance {
+        Distance::Hamming => {
+            check_lengths(ids)?;
+            matching_boxes(ids, matcher)
+        }
+        _ => edited_boxes(ids, distance),
+    };
+    let (left, right) = boxes.ok_or("no matching boxes found")?;
+
+    let left = graphemes(&left);
+    let right = graphemes(&right);
+    let solution = distance
+        .align(&left, &right, 1)
+        .expect("the boxes are one edit apart")
+        .into_iter()
+        .filter_map(|step| match step {
+            Step::Keep(c) => Some(c),
+            Step::Edit(_) => None,
+        })
+        .collect();
+
+    Ok(solution)
+}
+
+// Two IDs within some distance, and the letter positions of the left one
+// where they differ: substituted, deleted or transposed letters, or where
+// letters were inserted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearPair {
+    // indexes into the IDs, left < right
+    pub left: usize,
+    pub right: usize,
+    pub distance: usize,
+    pub differences: Vec<usize>,
+}
+
+// Every pair of IDs within `k` of each other, exact duplicates included, in
+// order of (left, right). IDs of different lengths are never within any
+// Hamming distance.
+pub fn near_pairs(ids: &[String], k: usize, distance: Distance) -> Vec<NearPair> {
+    let letters: Vec<Vec<&str>> = ids.iter().map(|id| graphemes(id)).collect();
+    let candidates = match distance {
+        Distance::Hamming => block_candidates(&letters, k),
+        _ => deletion_candidates(&letters, k),
+    };
+
+    let mut pairs: Vec<NearPair> = candidates
+        .into_iter()
+        .filter_map(|(left, right)| {
+            let steps = distance.align(&letters[left], &letters[right], k)?;
+            let mut differences: Vec<usize> = steps
+                .iter()
+                .filter_map(|step| match step {
+                    Step::Edit(i) => Some(*i),
+                    Step::Keep(_) => None,
+                })
+                .collect();
+            let distance = differences.len();
+            // several insertions can land in one place
+            differences.dedup();
+
+            Some(NearPair {
+                left,
+                right,
+                distance,
+                differences,
+            })
+        })
+        .collect();
+    pairs.sort_unstable_by_key(|pair| (pair.left, pair.right));
+
+    pairs
+}
+
+// Split each ID into k + 1 blocks: a pair with at most k differences must
+// agree on one block entirely, so only IDs sharing a block are compared
+fn block_candidates(letters: &[Vec<&str>], k: usize) -> HashSet<(usize, usize)> {
+    // (length, block, its letters) to the IDs with that block
+    let mut blocks: HashMap<(usize, usize, &[&str]), Vec<usize>> = HashMap::new();
+    for (i, id) in letters.iter().enumerate() {
+        let len = id.len();
+        for block in 0..=k.min(len) {
+            let (start, end) = (block * len / (k + 1), (block + 1) * len / (k + 1));
+            blocks
+                .entry((len, block, &id[start..end]))
+                .or_default()
+                .push(i);
+        }
+    }
+
+    grouped_pairs(blocks.values())
+}
+
+// Whatever k edits line two IDs up, deleting the edited letters from each
+// leaves the same string, so only IDs sharing a k deletion variant are
+// compared
+fn deletion_candidates(letters: &[Vec<&str>], k: usize) -> HashSet<(usize, usize)> {
+    let mut variants: HashMap<Vec<&str>, Vec<usize>> = HashMap::new();
+    for (i, id) in letters.iter().enumerate() {
+        let mut seen = HashSet::new();
+        seen.insert(id.clone());
+        let mut frontier = vec![id.clone()];
+        for _ in 0..k {
+            frontier = frontier
+                .iter()
+                .flat_map(|variant| {
+                    (0..variant.len()).map(move |d| {
+                        let mut shorter = variant.clone();
+                        shorter.remove(d);
+                        shorter
+                    })
+                })
+                .filter(|shorter| seen.insert(shorter.clone()))
+                .collect();
+        }
+
+        for variant in seen {
+            variants.entry(variant).or_default().push(i);
+        }
+    }
+
+    grouped_pairs(variants.values())
+}
+
+// Every pair within each group of ascending indexes
+fn grouped_pairs<'a, I>(groups: I) -> HashSet<(usize, usize)>
+where
+    I: Iterator<Item = &'a Vec<usize>>,
+{
+    let mut pairs = HashSet::new();
+    for group in groups {
+        for (n, &left) in group.iter().enumerate() {
+            pairs.extend(group[n + 1..].iter().map(|&right| (left, right)));
+        }
+    }
+
+    pairs
+}
+
+// One step of lining up two IDs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step<'a> {
+    Keep(&'a str),
+    // an edit at this position of the left ID
+    Edit(usize),
+}
+
+impl Distance {
+    // The cheapest way to line up two IDs, or None if it's more than
+    // `limit` edits
+    fn align<'a>(self, left: &[&'a str], right: &[&str], limit: usize) -> Option<Vec<Step<'a>>> {
+        if self == Distance::Hamming {
+            if left.len() != right.len() {
+                return None;
+            }
+            let steps: Vec<Step> = left
+                .iter()
+                .zip(right)
+                .enumerate()
+                .map(|(i, (&l, &r))| if l == r { Step::Keep(l) } else { Step::Edit(i) })
+                .collect();
+            let edits = steps
+                .iter()
+                .filter(|step| matches!(step, Step::Edit(_)))
+                .count();
+            return if edits > limit { None } else { Some(steps) };
+        }
+
+        if left.len().abs_diff(right.len()) > limit {
+            return None;
+        }
+        let costs = self.edit_costs(left, right);
+        let (mut i, mut j) = (left.len(), right.len());
+        if costs[i][j] > limit {
+            return None;
+        }
+
+        // walk back along a cheapest path
+        let mut steps = vec![];
+        while i > 0 || j > 0 {
+            let cost = costs[i][j];
+            if i > 0 && j > 0 && left[i - 1] == right[j - 1] && cost == costs[i - 1][j - 1] {
+                steps.push(Step::Keep(left[i - 1]));
+                i -= 1;
+                j -= 1;
+            } else if self.transposed(left, right, i, j) && cost == costs[i - 2][j - 2] + 1 {
+                steps.push(Step::Edit(i - 2));
+                i -= 2;
+                j -= 2;
+            } else if i > 0 && j > 0 && cost == costs[i - 1][j - 1] + 1 {
+                steps.push(Step::Edit(i - 1));
+                i -= 1;
+                j -= 1;
+            } else if i > 0 && cost == costs[i - 1][j] + 1 {
+                steps.push(Step::Edit(i - 1));
+                i -= 1;
+            } else {
+                steps.push(Step::Edit(i));
+                j -= 1;
+            }
+        }
+        steps.reverse();
+
+        Some(steps)
+    }
+
+    // costs[i][j] is the distance between the first i letters of `left` and
+    // the first j of `right`
+    fn edit_costs(self, left: &[&str], right: &[&str]) -> Vec<Vec<usize>> {
+        let mut costs = vec![vec![0; right.len() + 1]; left.len() + 1];
+        for (i, row) in costs.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cost) in costs[0].iter_mut().enumerate() {
+            *cost = j;
+        }
+
+        for i in 1..=left.len() {
+            for j in 1..=right.len() {
+                let substitute = costs[i - 1][j - 1] + usize::from(left[i - 1] != right[j - 1]);
+                let mut cost = substitute.min(costs[i - 1][j] + 1).min(costs[i][j - 1] + 1);
+                if self.transposed(left, right, i, j) {
+                    cost = cost.min(costs[i - 2][j - 2] + 1);
+                }
+                costs[i][j] = cost;
+            }
+        }
+
+        costs
+    }
+
+    // Whether the letters before i and j are the same two, swapped
+    fn transposed(self, left: &[&str], right: &[&str], i: usize, j: usize) -> bool {
+        self == Distance::Damerau
+            && i > 1
+            && j > 1
+            && left[i - 1] == right[j - 2]
+            && left[i - 2] == right[j - 1]
+    }
+}
+
+// The families of IDs linked by chains of near pairs, as sorted indexes into
+// the IDs. IDs with nothing within `k` aren't in any cluster.
+pub fn near_clusters(ids: &[String], k: usize, distance: Distance) -> Vec<Vec<usize>> {
+    // union-find, with each ID pointing towards its cluster's root
+    let mut parents: Vec<usize> = (0..ids.len()).collect();
+    for pair in near_pairs(ids, k, distance) {
+        let (left, right) = (
+            root(&mut parents, pair.left),
+            root(&mut parents, pair.right),
+        );
+        parents[left.max(right)] = left.min(right);
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..ids.len() {
+        let root = root(&mut parents, i);
+        clusters.entry(root).or_default().push(i);
+    }
+
+    let mut clusters: Vec<Vec<usize>> = clusters
+        .into_values()
+        .filter(|cluster| cluster.len() > 1)
+        .collect();
+    clusters.sort_unstable();
+
+    clusters
+}
+
+fn root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz";
+        let ids = Day2::default().parse(input).unwrap();
+        assert_eq!(
+            "fgij",
+            part_two(&ids, Matcher::Masked, Distance::Hamming).unwrap()
+        );
+        assert_eq!(
+            "fgij",
+            part_two(&ids, Matcher::Pairwise, Distance::Hamming).unwrap()
+        );
+    }
+
+    #[test]
+    fn matchers_agree() {
+        for seed in 0..5 {
+            let input = crate::generate::generate(2, seed, 0.5).unwrap();
+            let ids = Day2::default().parse(&input).unwrap();
+            assert_eq!(
+                part_two(&ids, Matcher::Masked, Distance::Hamming).unwrap(),
+                part_two(&ids, Matcher::Pairwise, Distance::Hamming).unwrap()
+            );
+        }
+
+        // duplicates aren't a pair, and neither is a two letter difference
+        let ids: Vec<String> = vec!["abc".into(), "abc".into(), "xbz".into()];
+        assert_eq!(masked_matching_boxes(&ids), None);
+        assert_eq!(pairwise_matching_boxes(&ids), None);
+
+        // long IDs, where hashing each whole mask would cost O(L) a lookup
+        let mut rng = crate::generate::Rng::new(3);
+        let mut ids: Vec<String> = (0..200)
+            .map(|_| {
+                (0..2000)
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let mut near = ids[17].clone().into_bytes();
+        near[1234] = if near[1234] == b'a' { b'b' } else { b'a' };
+        ids.push(String::from_utf8(near).unwrap());
+        let expected = Some((ids[17].clone(), ids[200].clone()));
+        assert_eq!(masked_matching_boxes(&ids), expected);
+    }
+
+    fn strings(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn near_pairs_within_k() {
+        let ids = strings(&[
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ]);
+        let pairs = near_pairs(&ids, 1, Distance::Hamming);
+        assert_eq!(
+            pairs,
+            vec![NearPair {
+                left: 1,
+                right: 4,
+                distance: 1,
+                differences: vec![2],
+            }]
+        );
+
+        let pairs = near_pairs(&ids, 2, Distance::Hamming);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!((pairs[0].left, pairs[0].right), (0, 5));
+        assert_eq!(pairs[0].differences, vec![1, 3]);
+
+        // different lengths are never near, duplicates always are
+        let ids = strings(&["ab", "abc", "ab"]);
+        assert_eq!(near_pairs(&ids, 3, Distance::Hamming).len(), 1);
+        assert_eq!(
+            near_pairs(&ids, 0, Distance::Hamming)[0].differences,
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn near_pairs_match_brute_force() {
+        let input = crate::generate::generate(2, 4, 0.2).unwrap();
+        let ids: Vec<String> = input.lines().map(|id| id[..6].to_string()).collect();
+
+        for k in 0..4 {
+            let mut expected = vec![];
+            for left in 0..ids.len() {
+                for right in left + 1..ids.len() {
+                    let d = count_differences(&ids[left], &ids[right]).unwrap();
+                    if d <= k {
+                        expected.push((left, right, d));
+                    }
+                }
+            }
+
+            let pairs: Vec<(usize, usize, usize)> = near_pairs(&ids, k, Distance::Hamming)
+                .iter()
+                .map(|pair| (pair.left, pair.right, pair.differences.len()))
+                .collect();
+            assert_eq!(pairs, expected);
+        }
+    }
+
+    #[test]
+    fn clusters() {
+        // aaaa - aaab - aabb chain at k = 1, bbbb alone until k = 2
+        let ids = strings(&["aabb", "zzzz", "aaaa", "bbbb", "aaab"]);
+        assert_eq!(
+            near_clusters(&ids, 1, Distance::Hamming),
+            vec![vec![0, 2, 4]]
+        );
+        assert_eq!(
+            near_clusters(&ids, 2, Distance::Hamming),
+            vec![vec![0, 2, 3, 4]]
+        );
+        assert_eq!(
+            near_clusters(&ids, 0, Distance::Hamming),
+            Vec::<Vec<usize>>::new()
+        );
+    }
+
+    #[test]
+    fn matcher_param() {
+        let mut params = Params::new(2);
+        params.set("matcher", "pairwise");
+        assert_eq!(
+            Day2::from_params(&params).unwrap().matcher,
+            Matcher::Pairwise
+        );
+        params.set("matcher", "fastest");
+        assert!(Day2::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn edit_distances() {
+        let ids = strings(&["abcd", "abd", "abdc", "xabcd"]);
+        let pairs = |distance| -> Vec<(usize, usize, usize, Vec<usize>)> {
+            near_pairs(&ids, 2, distance)
+                .into_iter()
+                .map(|pair| (pair.left, pair.right, pair.distance, pair.differences))
+                .collect()
+        };
+
+        assert_eq!(pairs(Distance::Hamming), vec![(0, 2, 2, vec![2, 3])]);
+        assert_eq!(
+            pairs(Distance::Levenshtein),
+            vec![
+                (0, 1, 1, vec![2]),
+                (0, 2, 2, vec![2, 3]),
+                (0, 3, 1, vec![0]),
+                (1, 2, 1, vec![3]),
+                (1, 3, 2, vec![0, 2]),
+            ]
+        );
+        assert_eq!(pairs(Distance::Damerau)[1], (0, 2, 1, vec![2]));
+
+        let ids = strings(&["xyz", "abcde", "abde"]);
+        assert_eq!(
+            part_two(&ids, Matcher::Masked, Distance::Levenshtein).unwrap(),
+            "abde"
+        );
+        let ids = strings(&["abcde", "bacde"]);
+        assert!(part_two(&ids, Matcher::Masked, Distance::Levenshtein).is_err());
+        assert_eq!(
+            part_two(&ids, Matcher::Masked, Distance::Damerau).unwrap(),
+            "cde"
+        );
+    }
+
+    #[test]
+    fn edit_pairs_match_brute_force() {
+        let input = crate::generate::generate(2, 5, 0.2).unwrap();
+        let ids: Vec<String> = input
+            .lines()
+            .enumerate()
+            .map(|(i, id)| id[..3 + i % 3].to_string())
+            .collect();
+        let letters: Vec<Vec<&str>> = ids.iter().map(|id| graphemes(id)).collect();
+
+        for &distance in &[Distance::Levenshtein, Distance::Damerau] {
+            for k in 0..3 {
+                let mut expected = vec![];
+                for left in 0..ids.len() {
+                    for right in left + 1..ids.len() {
+                        let cost = distance.edit_costs(&letters[left], &letters[right])
+                            [letters[left].len()][letters[right].len()];
+                        if cost <= k {
+                            expected.push((left, right, cost));
+                        }
+                    }
+                }
+
+                let pairs: Vec<(usize, usize, usize)> = near_pairs(&ids, k, distance)
+                    .iter()
+                    .map(|pair| (pair.left, pair.right, pair.distance))
+                    .collect();
+                assert_eq!(pairs, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn strict_lengths() {
+        assert_eq!(count_differences("abc", "abcd"), None);
+        assert!(!correct_boxes("abc", "abd_"));
+
+        let ids = strings(&["abcd", "abc", "abce"]);
+        let error = part_two(&ids, Matcher::Masked, Distance::Hamming).unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+
+        // one decomposed accent is one letter, so these are all four long
+        // and the accent is what differs
+        let ids = strings(&["cafe\u{301}", "zzzz", "cafe", "caf\u{e9}"]);
+        assert_eq!(count_differences(&ids[0], &ids[1]), Some(4));
+        for &matcher in &[Matcher::Masked, Matcher::Pairwise] {
+            assert_eq!(part_two(&ids, matcher, Distance::Hamming).unwrap(), "caf");
+        }
+        let pairs = near_pairs(&ids, 1, Distance::Levenshtein);
+        assert_eq!(pairs[0].differences, vec![3]);
+    }
+
+    #[test]
+    fn checksums() {
+        let ids = strings(&[
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ]);
+        assert_eq!(part_one(&ids).unwrap(), 12);
+
+        let report = Checksum::new(&[3, 2]).with_count(1).report(&ids).unwrap();
+        let factors: Vec<(usize, Vec<usize>)> = report
+            .factors
+            .into_iter()
+            .map(|factor| (factor.count, factor.ids))
+            .collect();
+        assert_eq!(
+            factors,
+            vec![
+                (1, vec![0, 1, 2, 3, 4, 5]),
+                (2, vec![1, 2, 4, 5]),
+                (3, vec![1, 3, 6]),
+            ]
+        );
+        assert_eq!(report.product, 72);
+
+        // two accented e's, not two e's and two accents
+        let ids = strings(&["e\u{301}xe\u{301}", "\u{e9}x\u{e9}"]);
+        let report = "2".parse::<Checksum>().unwrap().report(&ids).unwrap();
+        assert_eq!(report.factors[0].ids, vec![0, 1]);
+
+        assert!("2,,3".parse::<Checksum>().is_err());
+        assert!("0".parse::<Checksum>().is_err());
+        let mut params = Params::new(2);
+        params.set("checksum_counts", "4, 2,3");
+        let day = Day2::from_params(&params).unwrap();
+        assert_eq!(day.checksum.counts(), &[2, 3, 4]);
+    }
+
+    #[test]
+    fn correct_boxes_test() {
+        assert!(correct_boxes("fghij", "fguij"));
+    }
+}
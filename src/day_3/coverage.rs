@@ -0,0 +1,642 @@
+use std::str::FromStr;
+
+use super::Claim;
+
+// How to count the claims over each square inch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Engine {
+    // the grid unless the claimed fabric is huge next to the number of claims
+    #[default]
+    Auto,
+    // a counter per square inch, memory proportional to the claimed fabric
+    Grid,
+    // a sweep across the claims' edges, memory proportional to the claims
+    Sweep,
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Engine, String> {
+        match s {
+            "auto" => Ok(Engine::Auto),
+            "grid" => Ok(Engine::Grid),
+            "sweep" => Ok(Engine::Sweep),
+            _ => Err(format!("unknown engine '{}'", s)),
+        }
+    }
+}
+
+impl Engine {
+    // Never Auto
+    fn pick(self, rects: &[Rect]) -> Engine {
+        if self != Engine::Auto {
+            return self;
+        }
+
+        // a few counters per claim is fine, and so is a million regardless
+        let cells = Bounds::of(rects).map_or(0, |bounds| bounds.area());
+        if cells <= (1 << 20).max(32 * rects.len() as u64) {
+            Engine::Grid
+        } else {
+            Engine::Sweep
+        }
+    }
+}
+
+// The square inches [x0, x1) x [y0, y1); empty unless x0 < x1 and y0 < y1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub x0: i64,
+    pub y0: i64,
+    pub x1: i64,
+    pub y1: i64,
+}
+
+impl Rect {
+    pub fn is_empty(&self) -> bool {
+        self.x0 >= self.x1 || self.y0 >= self.y1
+    }
+
+    pub fn area(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.x1 - self.x0) as u64 * (self.y1 - self.y0) as u64
+        }
+    }
+
+    // Whether they share a square inch
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        Rect {
+            x0: self.x0.max(other.x0),
+            y0: self.y0.max(other.y0),
+            x1: self.x1.min(other.x1),
+            y1: self.y1.min(other.y1),
+        }
+    }
+}
+
+// Square inches within two or more claims
+pub fn overlap_area(claims: &[Claim], engine: Engine) -> u64 {
+    covered_area(claims, 2, engine)
+}
+
+// Square inches within at least `k` claims, so 1 for everything claimed.
+// Only the sweep is used for claims spread over a large fabric, and its cost
+// doesn't depend on how large.
+pub fn covered_area(claims: &[Claim], k: u32, engine: Engine) -> u64 {
+    assert!(k > 0, "every square inch is within at least 0 claims");
+    area_at_least(&rects(claims), k, engine)
+}
+
+// Square inches within at least `k` of `rects`
+pub(super) fn area_at_least(rects: &[Rect], k: u32, engine: Engine) -> u64 {
+    if k as usize > rects.len() {
+        return 0;
+    }
+
+    match engine.pick(rects) {
+        Engine::Grid => Grid::new(rects).area_at_least(k),
+        _ => sweep_area(rects, k as usize),
+    }
+}
+
+// Indexes of the claims that overlap no other claim, in order. Empty claims
+// overlap nothing.
+pub fn intact_claims(claims: &[Claim], engine: Engine) -> Vec<usize> {
+    let rects = rects(claims);
+    let overlapped = match engine.pick(&rects) {
+        Engine::Grid => Grid::new(&rects).overlapped(&rects),
+        _ => sweep_overlapped(&rects),
+    };
+
+    (0..rects.len()).filter(|&i| !overlapped[i]).collect()
+}
+
+fn rects(claims: &[Claim]) -> Vec<Rect> {
+    claims.iter().map(Claim::rect).collect()
+}
+
+// The smallest rectangle around every non-empty claim
+struct Bounds {
+    x0: i64,
+    y0: i64,
+    width: usize,
+    height: usize,
+}
+
+impl Bounds {
+    fn of(rects: &[Rect]) -> Option<Bounds> {
+        let mut rects = rects.iter().filter(|r| !r.is_empty());
+        let first = *rects.next()?;
+        let all = rects.fold(first, |all, r| Rect {
+            x0: all.x0.min(r.x0),
+            y0: all.y0.min(r.y0),
+            x1: all.x1.max(r.x1),
+            y1: all.y1.max(r.y1),
+        });
+
+        Some(Bounds {
+            x0: all.x0,
+            y0: all.y0,
+            width: (all.x1 - all.x0) as usize,
+            height: (all.y1 - all.y0) as usize,
+        })
+    }
+
+    fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+// How many claims cover each square inch inside the bounds, row by row, with
+// an extra column and row so a claim's far edges have somewhere to go
+pub(super) struct Grid {
+    bounds: Bounds,
+    counts: Vec<u32>,
+}
+
+impl Grid {
+    pub(super) fn new(rects: &[Rect]) -> Grid {
+        let bounds = Bounds::of(rects).unwrap_or(Bounds {
+            x0: 0,
+            y0: 0,
+            width: 0,
+            height: 0,
+        });
+        let stride = bounds.width + 1;
+        let mut counts = vec![0u32; stride * (bounds.height + 1)];
+
+        // +1 at a claim's corner and -1 past its far edges, so summing
+        // everything above and to the left of a square inch counts the
+        // claims over it. Counts never go negative once summed, so the
+        // wrapping in between is harmless.
+        for r in rects.iter().filter(|r| !r.is_empty()) {
+            let x0 = (r.x0 - bounds.x0) as usize;
+            let x1 = (r.x1 - bounds.x0) as usize;
+            let y0 = (r.y0 - bounds.y0) as usize;
+            let y1 = (r.y1 - bounds.y0) as usize;
+            for &(x, y, delta) in &[(x0, y0, 1), (x1, y0, !0), (x0, y1, !0), (x1, y1, 1)] {
+                counts[y * stride + x] = counts[y * stride + x].wrapping_add(delta);
+            }
+        }
+        prefix_sums(&mut counts, stride, u32::wrapping_add);
+
+        Grid { bounds, counts }
+    }
+
+    // Claims over the square inch at (x, y)
+    pub(super) fn count(&self, x: i64, y: i64) -> u32 {
+        let (x, y) = (x - self.bounds.x0, y - self.bounds.y0);
+        let inside = (0..self.bounds.width as i64).contains(&x)
+            && (0..self.bounds.height as i64).contains(&y);
+        if inside {
+            self.counts[y as usize * (self.bounds.width + 1) + x as usize]
+        } else {
+            0
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = u32> + '_ {
+        let stride = self.bounds.width + 1;
+        self.counts
+            .chunks(stride)
+            .take(self.bounds.height)
+            .flat_map(move |row| row[..stride - 1].iter().copied())
+    }
+
+    fn area_at_least(&self, k: u32) -> u64 {
+        self.cells().filter(|&count| count >= k).count() as u64
+    }
+
+    // Whether each claim has a square inch that's also someone else's
+    fn overlapped(&self, rects: &[Rect]) -> Vec<bool> {
+        // contested[y][x] is how many contested square inches there are
+        // above and to the left of (x, y), so any claim is checked in O(1)
+        let stride = self.bounds.width + 1;
+        let mut contested = vec![0u64; stride * (self.bounds.height + 1)];
+        for (i, count) in self.cells().enumerate() {
+            let (x, y) = (i % self.bounds.width, i / self.bounds.width);
+            contested[(y + 1) * stride + x + 1] = u64::from(count > 1);
+        }
+        prefix_sums(&mut contested, stride, |a, b| a + b);
+
+        rects
+            .iter()
+            .map(|r| {
+                if r.is_empty() {
+                    return false;
+                }
+                let x0 = (r.x0 - self.bounds.x0) as usize;
+                let x1 = (r.x1 - self.bounds.x0) as usize;
+                let y0 = (r.y0 - self.bounds.y0) as usize;
+                let y1 = (r.y1 - self.bounds.y0) as usize;
+                let at = |x: usize, y: usize| contested[y * stride + x];
+
+                at(x1, y1) + at(x0, y0) != at(x0, y1) + at(x1, y0)
+            })
+            .collect()
+    }
+}
+
+// In place 2D running totals of rows of `stride`
+fn prefix_sums<T: Copy>(values: &mut [T], stride: usize, add: impl Fn(T, T) -> T) {
+    for row in values.chunks_mut(stride) {
+        for x in 1..row.len() {
+            row[x] = add(row[x], row[x - 1]);
+        }
+    }
+    for i in stride..values.len() {
+        values[i] = add(values[i], values[i - stride]);
+    }
+}
+
+// The distinct y edges, so the sweep's trees only have a leaf per band
+// between neighbouring edges
+fn y_edges(rects: &[Rect]) -> Vec<i64> {
+    let mut ys: Vec<i64> = rects
+        .iter()
+        .filter(|r| !r.is_empty())
+        .flat_map(|r| vec![r.y0, r.y1])
+        .collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    ys
+}
+
+// The bands from y0 up to y1
+fn bands(ys: &[i64], r: &Rect) -> (usize, usize) {
+    let band = |y| ys.binary_search(&y).expect("an edge");
+    (band(r.y0), band(r.y1))
+}
+
+// Sweep left to right across the claims' left and right edges, keeping how
+// much of the current column is covered at least k times
+fn sweep_area(rects: &[Rect], k: usize) -> u64 {
+    let ys = y_edges(rects);
+    // (x, +1 or -1, index)
+    let mut edges: Vec<(i64, i32, usize)> = vec![];
+    for (i, r) in rects.iter().enumerate().filter(|(_, r)| !r.is_empty()) {
+        edges.push((r.x0, 1, i));
+        edges.push((r.x1, -1, i));
+    }
+    edges.sort_unstable();
+
+    // nothing to sweep for if no square inch is that deep
+    if k > max_depth(rects, &ys, &edges) {
+        return 0;
+    }
+
+    let mut tree = CoverTree::new(&ys, rects, k);
+    let mut area = 0;
+    let mut last_x = edges.first().map_or(0, |e| e.0);
+    for (x, delta, i) in edges {
+        area += tree.covered() * (x - last_x) as u64;
+        last_x = x;
+
+        let (low, high) = bands(&ys, &rects[i]);
+        tree.add(1, 0, ys.len() - 1, low, high, delta);
+    }
+
+    area
+}
+
+// The most claims over any one square inch, given sweep_area's edges. Ends
+// sort before starts at the same x, so the deepest point is always right
+// after a start.
+fn max_depth(rects: &[Rect], ys: &[i64], edges: &[(i64, i32, usize)]) -> usize {
+    let bands_len = ys.len().saturating_sub(1);
+    let mut active = MaxTree::new(bands_len, Combine::Add);
+    let mut deepest = 0;
+    for &(_, delta, i) in edges {
+        let (low, high) = bands(ys, &rects[i]);
+        active.update(low, high, i64::from(delta));
+        if delta > 0 {
+            deepest = deepest.max(active.max(0, bands_len) as usize);
+        }
+    }
+
+    deepest
+}
+
+// Sweep left to right: a claim meets every claim it overlaps at the later
+// of their left edges. So as a claim starts it's overlapped if anything
+// active covers its rows, and as it ends it's overlapped if anything started
+// in its rows after it did.
+fn sweep_overlapped(rects: &[Rect]) -> Vec<bool> {
+    let ys = y_edges(rects);
+    // (x, 0 for an end so ends go first, index)
+    let mut edges: Vec<(i64, u8, usize)> = vec![];
+    for (i, r) in rects.iter().enumerate().filter(|(_, r)| !r.is_empty()) {
+        edges.push((r.x0, 1, i));
+        edges.push((r.x1, 0, i));
+    }
+    edges.sort_unstable();
+
+    let bands_len = ys.len().saturating_sub(1);
+    // claims active over each band
+    let mut active = MaxTree::new(bands_len, Combine::Add);
+    // the last claim to start over each band, numbered from 1
+    let mut latest = MaxTree::new(bands_len, Combine::Max);
+    let mut started = vec![0; rects.len()];
+    let mut overlapped = vec![false; rects.len()];
+    for (n, (_, kind, i)) in edges.into_iter().enumerate() {
+        let (low, high) = bands(&ys, &rects[i]);
+        if kind == 1 {
+            overlapped[i] |= active.max(low, high) > 0;
+            active.update(low, high, 1);
+            started[i] = n as i64 + 1;
+            latest.update(low, high, started[i]);
+        } else {
+            overlapped[i] |= latest.max(low, high) > started[i];
+            active.update(low, high, -1);
+        }
+    }
+
+    overlapped
+}
+
+// Bands between y edges, tracking how much is covered at least 1..=k times.
+// A node only keeps the levels up to the number of claims that ever reach
+// it, as any deeper are always 0, so the tree takes O(n log n) memory
+// whatever k is. Each edge still recomputes the levels kept at the O(log n)
+// nodes it visits, up to min(k, n) of them near the root, which sweep_area
+// keeps to k no deeper than the claims actually go.
+struct CoverTree<'a> {
+    ys: &'a [i64],
+    k: usize,
+    // claims covering all of each node's bands, not counted further down
+    cover: Vec<usize>,
+    // levels kept for each node, and where they start in `lengths`
+    levels: Vec<usize>,
+    starts: Vec<usize>,
+    // for each node, the length covered at least j times for j in 1..=levels
+    lengths: Vec<u64>,
+}
+
+impl<'a> CoverTree<'a> {
+    fn new(ys: &'a [i64], rects: &[Rect], k: usize) -> CoverTree<'a> {
+        let nodes = 4 * ys.len().max(1);
+        let mut reach = vec![0; nodes];
+        if ys.len() > 1 {
+            for r in rects.iter().filter(|r| !r.is_empty()) {
+                let (low, high) = bands(ys, r);
+                count_reach(&mut reach, 1, 0, ys.len() - 1, low, high);
+            }
+        }
+
+        let levels: Vec<usize> = reach.iter().map(|&claims| claims.min(k)).collect();
+        let mut starts = Vec::with_capacity(nodes);
+        let mut total = 0;
+        for &kept in &levels {
+            starts.push(total);
+            total += kept;
+        }
+
+        CoverTree {
+            ys,
+            k,
+            cover: vec![0; nodes],
+            levels,
+            starts,
+            lengths: vec![0; total],
+        }
+    }
+
+    fn covered(&self) -> u64 {
+        self.length(1, self.k)
+    }
+
+    // The length of a node covered at least j times, for j from 1
+    fn length(&self, node: usize, j: usize) -> u64 {
+        if j > self.levels[node] {
+            0
+        } else {
+            self.lengths[self.starts[node] + j - 1]
+        }
+    }
+
+    // `delta` claims over bands low..high, within the node for lo..hi
+    fn add(&mut self, node: usize, lo: usize, hi: usize, low: usize, high: usize, delta: i32) {
+        if high <= lo || hi <= low {
+            return;
+        }
+        if low <= lo && hi <= high {
+            self.cover[node] = (self.cover[node] as i64 + i64::from(delta)) as usize;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.add(2 * node, lo, mid, low, high, delta);
+            self.add(2 * node + 1, mid, hi, low, high, delta);
+        }
+
+        let full = (self.ys[hi] - self.ys[lo]) as u64;
+        let cover = self.cover[node];
+        for j in 1..=self.levels[node] {
+            self.lengths[self.starts[node] + j - 1] = if cover >= j {
+                full
+            } else if hi - lo == 1 {
+                0
+            } else {
+                // the children still need j - cover more
+                self.length(2 * node, j - cover) + self.length(2 * node + 1, j - cover)
+            };
+        }
+    }
+}
+
+// Count a claim over bands low..high at every node its updates will visit
+fn count_reach(reach: &mut [usize], node: usize, lo: usize, hi: usize, low: usize, high: usize) {
+    if high <= lo || hi <= low {
+        return;
+    }
+    reach[node] += 1;
+    if lo < low || high < hi {
+        let mid = (lo + hi) / 2;
+        count_reach(reach, 2 * node, lo, mid, low, high);
+        count_reach(reach, 2 * node + 1, mid, hi, low, high);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Combine {
+    Add,
+    Max,
+}
+
+// Range updates and range maximums over `len` bands. Updates stay at the
+// nodes they cover instead of being pushed down, which works because they
+// combine the same way whatever order they're applied in.
+struct MaxTree {
+    len: usize,
+    combine: Combine,
+    // updates applied to all of each node's bands
+    tags: Vec<i64>,
+    // the maximum within each node, tags below it included
+    maxes: Vec<i64>,
+}
+
+impl MaxTree {
+    fn new(len: usize, combine: Combine) -> MaxTree {
+        let nodes = 4 * len.max(1);
+        MaxTree {
+            len,
+            combine,
+            tags: vec![0; nodes],
+            maxes: vec![0; nodes],
+        }
+    }
+
+    fn apply(&self, a: i64, b: i64) -> i64 {
+        match self.combine {
+            Combine::Add => a + b,
+            Combine::Max => a.max(b),
+        }
+    }
+
+    fn update(&mut self, low: usize, high: usize, value: i64) {
+        self.update_node(1, 0, self.len, low, high, value);
+    }
+
+    fn max(&self, low: usize, high: usize) -> i64 {
+        self.max_node(1, 0, self.len, low, high).unwrap_or(0)
+    }
+
+    fn update_node(
+        &mut self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        low: usize,
+        high: usize,
+        value: i64,
+    ) {
+        if high <= lo || hi <= low {
+            return;
+        }
+        if low <= lo && hi <= high {
+            self.tags[node] = self.apply(self.tags[node], value);
+            self.maxes[node] = self.apply(self.maxes[node], value);
+            return;
+        }
+
+        let mid = (lo + hi) / 2;
+        self.update_node(2 * node, lo, mid, low, high, value);
+        self.update_node(2 * node + 1, mid, hi, low, high, value);
+        let children = self.maxes[2 * node].max(self.maxes[2 * node + 1]);
+        self.maxes[node] = self.apply(children, self.tags[node]);
+    }
+
+    fn max_node(&self, node: usize, lo: usize, hi: usize, low: usize, high: usize) -> Option<i64> {
+        if high <= lo || hi <= low {
+            return None;
+        }
+        if low <= lo && hi <= high {
+            return Some(self.maxes[node]);
+        }
+
+        let mid = (lo + hi) / 2;
+        let left = self.max_node(2 * node, lo, mid, low, high);
+        let right = self.max_node(2 * node + 1, mid, hi, low, high);
+        let children = left.max(right)?;
+        Some(self.apply(children, self.tags[node]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(x: i32, y: i32, width: i32, height: i32) -> Claim {
+        Claim {
+            id: 0,
+            corner_x: x,
+            corner_y: y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn engines_agree() {
+        // two crossing bars, a third stacked on their middle, and one
+        // touching them edge to edge without overlapping
+        let claims = vec![
+            claim(0, 4, 10, 2),
+            claim(4, 0, 2, 10),
+            claim(3, 3, 4, 4),
+            claim(10, 0, 3, 3),
+        ];
+        for &engine in &[Engine::Grid, Engine::Sweep] {
+            assert_eq!(overlap_area(&claims, engine), 4 + 4 + 4);
+            assert_eq!(intact_claims(&claims, engine), vec![3]);
+        }
+
+        assert_eq!(overlap_area(&[], Engine::Sweep), 0);
+        assert_eq!(intact_claims(&[], Engine::Grid), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn at_least_k() {
+        // three nested squares, 9x9, 6x6 and 3x3
+        let claims = vec![claim(0, 0, 9, 9), claim(3, 3, 6, 6), claim(6, 6, 3, 3)];
+        for &engine in &[Engine::Grid, Engine::Sweep] {
+            let areas: Vec<u64> = (1..=4).map(|k| covered_area(&claims, k, engine)).collect();
+            assert_eq!(areas, vec![81, 36, 9, 0]);
+        }
+
+        // far apart and far too big to count square inch by square inch
+        let claims = vec![
+            claim(1_000_000, 2_000_000, 3_000_000, 4_000_000),
+            claim(2_000_000, 1_000_000, 4_000_000, 3_000_000),
+            claim(-2_000_000_000, 0, 2_000_000_000, 1),
+        ];
+        assert_eq!(Engine::Auto.pick(&rects(&claims)), Engine::Sweep);
+        let overlap = 2_000_000u64 * 2_000_000;
+        assert_eq!(covered_area(&claims, 2, Engine::Auto), overlap);
+        assert_eq!(
+            covered_area(&claims, 1, Engine::Auto),
+            2 * 12_000_000_000_000 - overlap + 2_000_000_000
+        );
+    }
+
+    #[test]
+    fn every_depth() {
+        let input = crate::generate::generate(3, 9, 1.0).unwrap();
+        let mut claims = crate::day_3::parse_claims(&input).unwrap();
+        // a stack deeper than anything generated
+        for i in 0..12 {
+            claims.push(claim(500 + i, 500 + i, 40, 40));
+        }
+
+        let rects = rects(&claims);
+        let grid = Grid::new(&rects);
+        let deepest = (1..).find(|&k| grid.area_at_least(k) == 0).unwrap() - 1;
+        assert!(deepest >= 12);
+        for k in 1..=deepest + 1 {
+            assert_eq!(
+                sweep_area(&rects, k as usize),
+                grid.area_at_least(k),
+                "k={}",
+                k
+            );
+        }
+        // past the deepest point without building a tree
+        assert_eq!(sweep_area(&rects, 1 << 40), 0);
+    }
+
+    #[test]
+    fn auto_picks_by_size() {
+        let small = [claim(0, 0, 1000, 1000).rect()];
+        assert_eq!(Engine::Auto.pick(&small), Engine::Grid);
+
+        let huge = [claim(0, 0, 1_000_000, 1_000_000).rect()];
+        assert_eq!(Engine::Auto.pick(&huge), Engine::Sweep);
+        assert_eq!(Engine::Grid.pick(&huge), Engine::Grid);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+
+use super::{Claim, ClaimIndex};
+
+// Two claims sharing fabric, by index into the claims
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overlap {
+    // left < right
+    pub left: usize,
+    pub right: usize,
+    // square inches they share
+    pub area: u64,
+}
+
+// Which claims overlap which, found through the claim index rather than by
+// comparing every pair
+pub struct OverlapGraph {
+    // ordered by (left, right)
+    overlaps: Vec<Overlap>,
+    // each claim's neighbours, in order
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl OverlapGraph {
+    pub fn new(claims: &[Claim]) -> OverlapGraph {
+        let index = ClaimIndex::new(claims);
+        let mut overlaps = vec![];
+        let mut neighbours = vec![vec![]; claims.len()];
+
+        for (left, claim) in claims.iter().enumerate() {
+            let rect = claim.rect();
+            for right in index.intersecting(&rect) {
+                if right == left {
+                    continue;
+                }
+                neighbours[left].push(right);
+                if left < right {
+                    let area = rect.intersection(&claims[right].rect()).area();
+                    overlaps.push(Overlap { left, right, area });
+                }
+            }
+        }
+
+        OverlapGraph {
+            overlaps,
+            neighbours,
+        }
+    }
+
+    pub fn overlaps(&self) -> &[Overlap] {
+        &self.overlaps
+    }
+
+    pub fn neighbours(&self, claim: usize) -> &[usize] {
+        &self.neighbours[claim]
+    }
+
+    // Groups of claims linked by chains of overlaps, each in order and
+    // ordered by their first claim. Intact claims aren't in any cluster.
+    pub fn clusters(&self) -> Vec<Vec<usize>> {
+        let mut clustered = vec![false; self.neighbours.len()];
+        let mut clusters = vec![];
+
+        for start in 0..self.neighbours.len() {
+            if clustered[start] || self.neighbours[start].is_empty() {
+                continue;
+            }
+
+            let mut cluster = vec![];
+            let mut queue = VecDeque::new();
+            clustered[start] = true;
+            queue.push_back(start);
+            while let Some(claim) = queue.pop_front() {
+                cluster.push(claim);
+                for &next in &self.neighbours[claim] {
+                    if !clustered[next] {
+                        clustered[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            cluster.sort_unstable();
+            clusters.push(cluster);
+        }
+
+        clusters
+    }
+
+    // The cluster with the most claims, the earliest on a tie
+    pub fn largest_cluster(&self) -> Option<Vec<usize>> {
+        self.clusters()
+            .into_iter()
+            .rev()
+            .max_by_key(|cluster| cluster.len())
+    }
+
+    // A line per overlap of the two claims' IDs and the square inches they
+    // share, eg "1 2 4"
+    pub fn edge_list(&self, claims: &[Claim]) -> String {
+        self.overlaps
+            .iter()
+            .map(|o| format!("{} {} {}\n", claims[o.left].id, claims[o.right].id, o.area))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_3::parse_claims;
+
+    #[test]
+    fn example() {
+        let claims = parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap();
+        let graph = OverlapGraph::new(&claims);
+        assert_eq!(
+            graph.overlaps(),
+            &[Overlap {
+                left: 0,
+                right: 1,
+                area: 4
+            }]
+        );
+        assert_eq!(graph.neighbours(1), &[0]);
+        assert_eq!(graph.neighbours(2), &[] as &[usize]);
+        assert_eq!(graph.clusters(), vec![vec![0, 1]]);
+        assert_eq!(graph.edge_list(&claims), "1 2 4\n");
+    }
+
+    #[test]
+    fn chains_and_clusters() {
+        // 1-2-3 in a row, 4 alone, 5-6 apart from them, 7 on 1 and 3
+        let input = "#1 @ 0,0: 3x3\n#2 @ 2,0: 3x3\n#3 @ 4,0: 3x3\n#4 @ 20,20: 1x1\n\
+                     #5 @ 10,10: 2x2\n#6 @ 11,11: 2x2\n#7 @ 1,2: 5x1";
+        let claims = parse_claims(input).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        assert_eq!(graph.neighbours(0), &[1, 6]);
+        assert_eq!(graph.neighbours(6), &[0, 1, 2]);
+        assert_eq!(graph.clusters(), vec![vec![0, 1, 2, 6], vec![4, 5]]);
+        assert_eq!(graph.largest_cluster(), Some(vec![0, 1, 2, 6]));
+        assert_eq!(
+            graph.edge_list(&claims),
+            "1 2 3\n1 7 2\n2 3 3\n2 7 3\n3 7 2\n5 6 1\n"
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let input = crate::generate::generate(3, 8, 0.3).unwrap();
+        let claims = parse_claims(&input).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        let mut expected = vec![];
+        for left in 0..claims.len() {
+            for right in left + 1..claims.len() {
+                let area = claims[left]
+                    .rect()
+                    .intersection(&claims[right].rect())
+                    .area();
+                if area > 0 {
+                    expected.push(Overlap { left, right, area });
+                }
+            }
+        }
+        assert_eq!(graph.overlaps(), &expected[..]);
+
+        // everything but the one intact claim is in a cluster
+        let clustered: usize = graph.clusters().iter().map(Vec::len).sum();
+        assert_eq!(clustered, claims.len() - 1);
+    }
+}
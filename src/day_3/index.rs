@@ -0,0 +1,198 @@
+use super::coverage::{self, Engine, Rect};
+use super::Claim;
+
+// Children per node
+const FANOUT: usize = 16;
+
+// An R-tree over the claims, bulk loaded once by sort-tile-recursive
+// packing: sort by x into vertical slabs, sort each slab by y, then group
+// runs of FANOUT into nodes, level by level up to a single root.
+pub struct ClaimIndex {
+    // each non-empty claim's rect and index into the claims, in leaf order
+    entries: Vec<(Rect, usize)>,
+    // levels[0] groups entries and each level groups the one below it; the
+    // last level is the root
+    levels: Vec<Vec<Node>>,
+}
+
+struct Node {
+    bounds: Rect,
+    // children in the level below, or entries for level 0
+    start: usize,
+    end: usize,
+}
+
+impl ClaimIndex {
+    pub fn new(claims: &[Claim]) -> ClaimIndex {
+        let mut entries: Vec<(Rect, usize)> = claims
+            .iter()
+            .map(Claim::rect)
+            .enumerate()
+            .filter(|(_, rect)| !rect.is_empty())
+            .map(|(i, rect)| (rect, i))
+            .collect();
+
+        let mut levels = vec![];
+        if !entries.is_empty() {
+            levels.push(pack(&mut entries, |entry| entry.0));
+        }
+        while levels
+            .last()
+            .is_some_and(|level: &Vec<Node>| level.len() > 1)
+        {
+            let below = levels.last_mut().expect("a level");
+            let above = pack(below, |node| node.bounds);
+            levels.push(above);
+        }
+
+        ClaimIndex { entries, levels }
+    }
+
+    // Indexes of the claims covering the square inch at (x, y), in order
+    pub fn covering(&self, x: i64, y: i64) -> Vec<usize> {
+        self.intersecting(&Rect {
+            x0: x,
+            y0: y,
+            x1: x + 1,
+            y1: y + 1,
+        })
+    }
+
+    // Indexes of the claims sharing a square inch with `area`, in order
+    pub fn intersecting(&self, area: &Rect) -> Vec<usize> {
+        let mut found = vec![];
+        self.search(area, |&(_, i)| found.push(i));
+        found.sort_unstable();
+
+        found
+    }
+
+    // Square inches of `area` within two or more claims
+    pub fn contested_area(&self, area: &Rect) -> u64 {
+        let mut clipped = vec![];
+        self.search(area, |(rect, _)| clipped.push(rect.intersection(area)));
+
+        coverage::area_at_least(&clipped, 2, Engine::Auto)
+    }
+
+    fn search<F: FnMut(&(Rect, usize))>(&self, area: &Rect, mut found: F) {
+        let top = match self.levels.len() {
+            0 => return,
+            len => len - 1,
+        };
+
+        // (level, node) still to look in
+        let mut stack = vec![(top, 0)];
+        while let Some((level, n)) = stack.pop() {
+            let node = &self.levels[level][n];
+            if !node.bounds.intersects(area) {
+                continue;
+            }
+
+            if level == 0 {
+                self.entries[node.start..node.end]
+                    .iter()
+                    .filter(|entry| entry.0.intersects(area))
+                    .for_each(&mut found);
+            } else {
+                stack.extend((node.start..node.end).map(|child| (level - 1, child)));
+            }
+        }
+    }
+}
+
+// Reorder `items` into tiles and return a node per FANOUT of them
+fn pack<T, F: Fn(&T) -> Rect>(items: &mut [T], rect_of: F) -> Vec<Node> {
+    let nodes = items.len().div_ceil(FANOUT);
+    let slabs = (nodes as f64).sqrt().ceil() as usize;
+
+    // twice the centre, to stay in integers
+    let centre_x = |item: &T| rect_of(item).x0 + rect_of(item).x1;
+    let centre_y = |item: &T| rect_of(item).y0 + rect_of(item).y1;
+    items.sort_by_key(centre_x);
+    for slab in items.chunks_mut(slabs * FANOUT) {
+        slab.sort_by_key(centre_y);
+    }
+
+    (0..nodes)
+        .map(|n| {
+            let (start, end) = (n * FANOUT, ((n + 1) * FANOUT).min(items.len()));
+            let bounds = items[start + 1..end].iter().map(&rect_of).fold(
+                rect_of(&items[start]),
+                |all, r| Rect {
+                    x0: all.x0.min(r.x0),
+                    y0: all.y0.min(r.y0),
+                    x1: all.x1.max(r.x1),
+                    y1: all.y1.max(r.y1),
+                },
+            );
+            Node { bounds, start, end }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_3::parse_claims;
+    use crate::generate::{generate, Rng};
+
+    #[test]
+    fn queries_match_brute_force() {
+        let input = generate(3, 6, 1.0).unwrap();
+        let claims = parse_claims(&input).unwrap();
+        let index = ClaimIndex::new(&claims);
+        let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
+
+        let mut rng = Rng::new(6);
+        for _ in 0..200 {
+            let (x, y) = (rng.range(-5, 1005), rng.range(-5, 1005));
+            let expected: Vec<usize> = (0..rects.len())
+                .filter(|&i| {
+                    let r = rects[i];
+                    r.x0 <= x && x < r.x1 && r.y0 <= y && y < r.y1
+                })
+                .collect();
+            assert_eq!(index.covering(x, y), expected);
+
+            let area = Rect {
+                x0: x,
+                y0: y,
+                x1: x + rng.range(0, 30),
+                y1: y + rng.range(0, 30),
+            };
+            let expected: Vec<usize> = (0..rects.len())
+                .filter(|&i| rects[i].intersects(&area))
+                .collect();
+            assert_eq!(index.intersecting(&area), expected);
+
+            let mut contested = 0;
+            for x in area.x0..area.x1 {
+                for y in area.y0..area.y1 {
+                    contested += (index.covering(x, y).len() > 1) as u64;
+                }
+            }
+            assert_eq!(index.contested_area(&area), contested);
+        }
+    }
+
+    #[test]
+    fn example() {
+        let claims = parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap();
+        let index = ClaimIndex::new(&claims);
+        assert_eq!(index.covering(3, 3), vec![0, 1]);
+        assert_eq!(index.covering(0, 0), Vec::<usize>::new());
+
+        let everything = Rect {
+            x0: 0,
+            y0: 0,
+            x1: 10,
+            y1: 10,
+        };
+        assert_eq!(index.intersecting(&everything), vec![0, 1, 2]);
+        assert_eq!(index.contested_area(&everything), 4);
+
+        let empty = ClaimIndex::new(&[]);
+        assert_eq!(empty.contested_area(&everything), 0);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use super::Claim;
+
+// What to do with claims that parse but can't be real
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Validation {
+    // refuse the input, listing every bad claim
+    #[default]
+    Strict,
+    // warn about bad claims and solve without them
+    Lenient,
+}
+
+impl FromStr for Validation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Validation, String> {
+        match s {
+            "strict" => Ok(Validation::Strict),
+            "lenient" => Ok(Validation::Lenient),
+            _ => Err(format!("unknown validation '{}'", s)),
+        }
+    }
+}
+
+// Something wrong with the claim on a line of input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    // 1-based
+    pub line: usize,
+    pub id: i32,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: claim #{} {}", self.line, self.id, self.message)
+    }
+}
+
+impl Claim {
+    // A claim that checks out, unlike Claim::parse which only checks syntax
+    pub fn new(
+        id: i32,
+        corner_x: i32,
+        corner_y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Claim, String> {
+        let claim = Claim {
+            id,
+            corner_x,
+            corner_y,
+            width,
+            height,
+        };
+        match claim.problems().as_slice() {
+            [] => Ok(claim),
+            problems => Err(format!("claim #{} {}", id, problems.join(", "))),
+        }
+    }
+
+    // Everything wrong with the claim on its own
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        if self.id <= 0 {
+            problems.push("has an ID below 1".to_string());
+        }
+        if self.corner_x < 0 || self.corner_y < 0 {
+            problems.push(format!(
+                "starts off the fabric at {},{}",
+                self.corner_x, self.corner_y
+            ));
+        }
+        if self.width <= 0 || self.height <= 0 {
+            problems.push(format!("is {}x{}", self.width, self.height));
+        }
+        if self.corner_x.checked_add(self.width).is_none()
+            || self.corner_y.checked_add(self.height).is_none()
+        {
+            problems.push("runs past the edge of an i32".to_string());
+        }
+
+        problems
+    }
+}
+
+// Every problem with claims parsed a line each, in line order. A repeated ID
+// is blamed on the later lines.
+pub fn lint(claims: &[Claim]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut first_line = HashMap::new();
+
+    for (i, claim) in claims.iter().enumerate() {
+        let line = i + 1;
+        let problem = |message| Problem {
+            line,
+            id: claim.id,
+            message,
+        };
+        problems.extend(claim.problems().into_iter().map(problem));
+
+        match first_line.get(&claim.id) {
+            Some(first) => problems.push(problem(format!("repeats the ID from line {}", first))),
+            None => {
+                first_line.insert(claim.id, line);
+            }
+        }
+    }
+
+    problems
+}
+
+// Apply `validation` to claims parsed a line each, giving the claims kept
+// and the problems with any that were skipped
+pub fn validate(
+    claims: Vec<Claim>,
+    validation: Validation,
+) -> Result<(Vec<Claim>, Vec<Problem>), String> {
+    let problems = lint(&claims);
+    if problems.is_empty() {
+        return Ok((claims, problems));
+    }
+
+    match validation {
+        Validation::Strict => {
+            let listed: Vec<String> = problems.iter().map(Problem::to_string).collect();
+            Err(format!(
+                "day 3 input has bad claims, try --param validation=lenient to skip them\n{}",
+                listed.join("\n")
+            ))
+        }
+        Validation::Lenient => {
+            let bad: HashSet<usize> = problems.iter().map(|p| p.line).collect();
+            let kept = claims
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| !bad.contains(&(i + 1)))
+                .map(|(_, claim)| claim)
+                .collect();
+            Ok((kept, problems))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_3::parse_claims;
+
+    const BAD_INPUT: &str = "#1 @ 1,3: 4x4\n\
+                             #2 @ -1,3: 0x4\n\
+                             #1 @ 5,5: 2x2\n\
+                             #0 @ 5,5: 2x2\n\
+                             #4 @ 2147483647,0: 1x1\n\
+                             #5 @ 3,1: 4x4";
+
+    #[test]
+    fn constructor() {
+        assert!(Claim::new(1, 1, 3, 4, 4).is_ok());
+        assert_eq!(
+            Claim::new(2, -1, 3, 0, 4).unwrap_err(),
+            "claim #2 starts off the fabric at -1,3, is 0x4"
+        );
+        assert!(Claim::new(-7, 0, 0, 1, 1).is_err());
+    }
+
+    #[test]
+    fn lints() {
+        let claims = parse_claims(BAD_INPUT).unwrap();
+        let found: Vec<String> = lint(&claims).iter().map(Problem::to_string).collect();
+        assert_eq!(
+            found,
+            vec![
+                "line 2: claim #2 starts off the fabric at -1,3",
+                "line 2: claim #2 is 0x4",
+                "line 3: claim #1 repeats the ID from line 1",
+                "line 4: claim #0 has an ID below 1",
+                "line 5: claim #4 runs past the edge of an i32",
+            ]
+        );
+
+        let input = crate::generate::generate(3, 1, 1.0).unwrap();
+        assert_eq!(lint(&parse_claims(&input).unwrap()), vec![]);
+    }
+
+    #[test]
+    fn modes() {
+        let error = validate(parse_claims(BAD_INPUT).unwrap(), Validation::Strict).unwrap_err();
+        assert!(error.starts_with("day 3 input has bad claims"));
+        assert_eq!(error.lines().count(), 6);
+
+        let (kept, problems) =
+            validate(parse_claims(BAD_INPUT).unwrap(), Validation::Lenient).unwrap();
+        let ids: Vec<i32> = kept.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 5]);
+        assert_eq!(problems.len(), 5);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::error::{parse_lines, AmbiguousAnswer, LineError, ParseError};
+use crate::params::Params;
+use crate::parser::whole;
+use crate::{Solution, StdResult};
+
+pub mod coverage;
+pub mod graph;
+pub mod index;
+pub mod lint;
+pub mod render;
+
+pub use coverage::{Engine, Rect};
+pub use graph::OverlapGraph;
+pub use index::ClaimIndex;
+pub use lint::{Problem, Validation};
+
+pub struct Day3 {
+    pub engine: Engine,
+    // part one counts square inches within at least this many claims
+    pub at_least: u32,
+    pub validation: Validation,
+}
+
+impl Default for Day3 {
+    fn default() -> Day3 {
+        Day3 {
+            engine: Engine::default(),
+            at_least: 2,
+            validation: Validation::default(),
+        }
+    }
+}
+
+impl Day3 {
+    pub fn from_params(params: &Params) -> StdResult<Day3> {
+        params.expect_only(&["engine", "at_least", "validation"])?;
+        let default = Day3::default();
+
+        let at_least = params.get("at_least")?.unwrap_or(default.at_least);
+        if at_least == 0 {
+            Err("day 3 needs at_least to be 1 or more")?
+        }
+
+        Ok(Day3 {
+            engine: params.get("engine")?.unwrap_or(default.engine),
+            at_least,
+            validation: params.get("validation")?.unwrap_or(default.validation),
+        })
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Claims;
+    type PartOne = u64;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> StdResult<Claims> {
+        let (claims, skipped) = lint::validate(parse_claims(input)?, self.validation)?;
+        Ok(Claims { claims, skipped })
+    }
+
+    fn part_one(&self, input: &Claims) -> StdResult<u64> {
+        Ok(coverage::covered_area(
+            &input.claims,
+            self.at_least,
+            self.engine,
+        ))
+    }
+
+    fn part_two(&self, input: &Claims) -> StdResult<i32> {
+        part_two(&input.claims, self.engine)
+    }
+
+    fn warnings(&self, input: &Claims) -> Vec<String> {
+        input
+            .skipped
+            .iter()
+            .map(|problem| format!("skipped {}", problem))
+            .collect()
+    }
+}
+
+// The claims to solve with, after lenient validation skipped any bad ones
+pub struct Claims {
+    pub claims: Vec<Claim>,
+    pub skipped: Vec<Problem>,
+}
+
+#[derive(Debug, Default)]
+pub struct Claim {
+    pub id: i32,
+    pub corner_x: i32,
+    pub corner_y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Claim {
+    // eg "#1 @ 1,3: 4x4"
+    pub fn parse(line: &str) -> Result<Claim, LineError> {
+        whole(line, |p| {
+            p.literal("#")?;
+            let id = p.integer()?;
+            p.literal(" @ ")?;
+            let corner_x = p.integer()?;
+            p.literal(",")?;
+            let corner_y = p.integer()?;
+            p.literal(": ")?;
+            let width = p.integer()?;
+            p.literal("x")?;
+            let height = p.integer()?;
+
+            Ok(Claim {
+                id,
+                corner_x,
+                corner_y,
+                width,
+                height,
+            })
+        })
+    }
+
+    pub fn rect(&self) -> Rect {
+        let (x, y) = (i64::from(self.corner_x), i64::from(self.corner_y));
+        Rect {
+            x0: x,
+            y0: y,
+            x1: x + i64::from(self.width),
+            y1: y + i64::from(self.height),
+        }
+    }
+}
+
+pub fn parse_claims(input: &str) -> Result<Vec<Claim>, ParseError> {
+    parse_lines(3, input, Claim::parse)
+}
+
+pub fn part_one(claims: &[Claim], engine: Engine) -> u64 {
+    coverage::overlap_area(claims, engine)
+}
+
+// The IDs of the claims no other claim overlaps, in input order
+pub fn intact_ids(claims: &[Claim], engine: Engine) -> Vec<i32> {
+    coverage::intact_claims(claims, engine)
+        .into_iter()
+        .map(|i| claims[i].id)
+        .collect()
+}
+
+// The puzzle promises exactly one intact claim, so several are an error
+// listing them all rather than a pick of one
+pub fn part_two(claims: &[Claim], engine: Engine) -> StdResult<i32> {
+    match intact_ids(claims, engine).as_slice() {
+        [] => Err("no 'intact' claim found")?,
+        &[id] => Ok(id),
+        ids => Err(AmbiguousAnswer {
+            candidates: ids.iter().map(i32::to_string).collect(),
+        })?,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const EXAMPLE_INPUT: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+    #[test]
+    fn parsing() {
+        let input = "#1 @ 1,3: 4x4";
+        let claim = Claim::parse(input).unwrap();
+        assert_eq!(claim.id, 1);
+        assert_eq!(claim.corner_x, 1);
+        assert_eq!(claim.corner_y, 3);
+        assert_eq!(claim.width, 4);
+        assert_eq!(claim.height, 4);
+    }
+
+    #[test]
+    fn at_least_param() {
+        let mut params = Params::new(3);
+        params.set("at_least", "1");
+        let day = Day3::from_params(&params).unwrap();
+        let claims = day.parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(day.part_one(&claims).unwrap(), 16 + 16 - 4 + 4);
+
+        params.set("at_least", "0");
+        assert!(Day3::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4y4").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 12);
+        assert_eq!(error.expected, "'x'");
+
+        // split-based parsing used to accept these
+        assert!(Claim::parse("#1 @ 1,3:  4x4").is_err());
+        assert!(Claim::parse("#1 @ 1,3: 4x4 extra").is_err());
+    }
+
+    // the old square inch by square inch count
+    fn fabric_map(claims: &[Claim]) -> HashMap<(i32, i32), i32> {
+        let mut fabric = HashMap::new();
+        for claim in claims {
+            for x in claim.corner_x..(claim.corner_x + claim.width) {
+                for y in claim.corner_y..(claim.corner_y + claim.height) {
+                    *fabric.entry((x, y)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        fabric
+    }
+
+    #[test]
+    fn part_one_example() {
+        let claims = parse_claims(EXAMPLE_INPUT).unwrap();
+        for &engine in &[Engine::Grid, Engine::Sweep] {
+            assert_eq!(4, part_one(&claims, engine));
+        }
+    }
+
+    #[test]
+    fn part_two_example() {
+        let claims = parse_claims(EXAMPLE_INPUT).unwrap();
+        for &engine in &[Engine::Grid, Engine::Sweep] {
+            assert_eq!(part_two(&claims, engine).unwrap(), 3);
+        }
+    }
+
+    #[test]
+    fn ambiguous_part_two() {
+        let mut claims = parse_claims(EXAMPLE_INPUT).unwrap();
+        claims.push(Claim::parse("#4 @ 8,0: 1x1").unwrap());
+        for &engine in &[Engine::Grid, Engine::Sweep] {
+            assert_eq!(intact_ids(&claims, engine), vec![3, 4]);
+            let error = part_two(&claims, engine).unwrap_err();
+            let ambiguous = error.downcast_ref::<AmbiguousAnswer>().unwrap();
+            assert_eq!(ambiguous.candidates, vec!["3", "4"]);
+        }
+
+        claims.push(Claim::parse("#5 @ 0,0: 9x9").unwrap());
+        assert!(part_two(&claims, Engine::Auto).is_err());
+    }
+
+    #[test]
+    fn engines_match_fabric_map() {
+        for seed in 0..4 {
+            let input = crate::generate::generate(3, seed, 0.3).unwrap();
+            let mut claims = parse_claims(&input).unwrap();
+            // a few odd ones: empty, off the fabric, and a triple overlap
+            claims.push(Claim::parse("#9001 @ 5,5: 0x7").unwrap());
+            claims.push(Claim::parse("#9002 @ -20,-3: 4x2").unwrap());
+            claims.push(Claim::parse("#9003 @ 100,100: 30x30").unwrap());
+            claims.push(Claim::parse("#9004 @ 110,110: 30x30").unwrap());
+
+            let fabric = fabric_map(&claims);
+            let overlap = fabric.values().filter(|&&v| v > 1).count();
+            let tripled = fabric.values().filter(|&&v| v > 2).count();
+            let intact: Vec<usize> = (0..claims.len())
+                .filter(|&i| {
+                    let c = &claims[i];
+                    (c.corner_x..c.corner_x + c.width)
+                        .all(|x| (c.corner_y..c.corner_y + c.height).all(|y| fabric[&(x, y)] == 1))
+                })
+                .collect();
+
+            for &engine in &[Engine::Grid, Engine::Sweep] {
+                assert_eq!(part_one(&claims, engine), overlap as u64);
+                assert_eq!(coverage::intact_claims(&claims, engine), intact);
+                assert_eq!(
+                    coverage::covered_area(&claims, 1, engine),
+                    fabric.len() as u64
+                );
+                assert_eq!(coverage::covered_area(&claims, 3, engine), tripled as u64);
+            }
+        }
+    }
+}
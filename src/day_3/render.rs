@@ -0,0 +1,189 @@
+use super::coverage::{self, Engine, Grid, Rect};
+use super::Claim;
+use crate::image::{Image, Rgb};
+use crate::StdResult;
+
+// Past this many square inches, pictures are too big to be useful
+const MAX_AREA: u64 = 1 << 26;
+
+const UNCLAIMED: Rgb = [255, 255, 255];
+const CLAIMED: Rgb = [160, 200, 255];
+const INTACT: Rgb = [0, 170, 60];
+// contested square inches shade from the first to the second
+const CONTESTED: (Rgb, Rgb) = ([255, 210, 0], [170, 0, 0]);
+
+// The fabric the way the puzzle draws it: '.' unclaimed, '#' claimed once
+// and 'X' claimed more than once, with the intact claims drawn in 'O'
+pub fn ascii(claims: &[Claim]) -> StdResult<String> {
+    let fabric = Fabric::new(claims)?;
+
+    let mut text = String::new();
+    for y in fabric.area.y0..fabric.area.y1 {
+        for x in fabric.area.x0..fabric.area.x1 {
+            text.push(match fabric.grid.count(x, y) {
+                0 => '.',
+                1 if fabric.is_intact(x, y) => 'O',
+                1 => '#',
+                _ => 'X',
+            });
+        }
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+// A pixel per square inch, coloured by how many claims cover it and with the
+// intact claims in green
+pub fn heatmap(claims: &[Claim]) -> StdResult<Image> {
+    let fabric = Fabric::new(claims)?;
+    let width = (fabric.area.x1 - fabric.area.x0) as usize;
+    let height = (fabric.area.y1 - fabric.area.y0) as usize;
+
+    let deepest = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| fabric.count_at(x, y))
+        .max()
+        .unwrap_or(0);
+
+    let mut image = Image::new(width, height, UNCLAIMED);
+    for y in 0..height {
+        for x in 0..width {
+            let count = fabric.count_at(x, y);
+            let colour = match count {
+                0 => UNCLAIMED,
+                1 if fabric.is_intact(fabric.area.x0 + x as i64, fabric.area.y0 + y as i64) => {
+                    INTACT
+                }
+                1 => CLAIMED,
+                _ => shade(count - 2, deepest.saturating_sub(2)),
+            };
+            image.set(x, y, colour);
+        }
+    }
+
+    Ok(image)
+}
+
+// `level` of `levels` along the contested colours
+fn shade(level: u32, levels: u32) -> Rgb {
+    let t = if levels == 0 {
+        0.0
+    } else {
+        f64::from(level) / f64::from(levels)
+    };
+    let (from, to) = CONTESTED;
+    let mut colour = [0; 3];
+    for i in 0..3 {
+        colour[i] =
+            (f64::from(from[i]) + t * (f64::from(to[i]) - f64::from(from[i]))).round() as u8;
+    }
+
+    colour
+}
+
+// The counts over the fabric to draw: from the origin, as in the puzzle's
+// pictures, to a square inch past the furthest claim
+struct Fabric {
+    grid: Grid,
+    area: Rect,
+    intact: Vec<Rect>,
+}
+
+impl Fabric {
+    fn new(claims: &[Claim]) -> StdResult<Fabric> {
+        let rects: Vec<Rect> = claims
+            .iter()
+            .map(Claim::rect)
+            .filter(|r| !r.is_empty())
+            .collect();
+        let area = rects.iter().fold(
+            Rect {
+                x0: 0,
+                y0: 0,
+                x1: 1,
+                y1: 1,
+            },
+            |area, r| Rect {
+                x0: area.x0.min(r.x0),
+                y0: area.y0.min(r.y0),
+                x1: area.x1.max(r.x1 + 1),
+                y1: area.y1.max(r.y1 + 1),
+            },
+        );
+        if area.area() > MAX_AREA {
+            Err(format!(
+                "the fabric is {}x{}, too large to draw",
+                area.x1 - area.x0,
+                area.y1 - area.y0
+            ))?
+        }
+
+        let intact = coverage::intact_claims(claims, Engine::Grid)
+            .into_iter()
+            .map(|i| claims[i].rect())
+            .collect();
+
+        Ok(Fabric {
+            grid: Grid::new(&rects),
+            area,
+            intact,
+        })
+    }
+
+    // By position in the picture rather than on the fabric
+    fn count_at(&self, x: usize, y: usize) -> u32 {
+        self.grid
+            .count(self.area.x0 + x as i64, self.area.y0 + y as i64)
+    }
+
+    fn is_intact(&self, x: i64, y: i64) -> bool {
+        self.intact
+            .iter()
+            .any(|r| r.x0 <= x && x < r.x1 && r.y0 <= y && y < r.y1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_3::parse_claims;
+
+    const EXAMPLE_INPUT: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+    #[test]
+    fn example_ascii() {
+        let claims = parse_claims(EXAMPLE_INPUT).unwrap();
+        let expected = "........\n\
+                        ...####.\n\
+                        ...####.\n\
+                        .##XX##.\n\
+                        .##XX##.\n\
+                        .####OO.\n\
+                        .####OO.\n\
+                        ........\n";
+        assert_eq!(ascii(&claims).unwrap(), expected);
+        assert_eq!(ascii(&[]).unwrap(), ".\n");
+    }
+
+    #[test]
+    fn example_heatmap() {
+        let mut claims = parse_claims(EXAMPLE_INPUT).unwrap();
+        claims.push(Claim::parse("#4 @ 4,4: 1x1").unwrap());
+        let image = heatmap(&claims).unwrap();
+
+        assert_eq!((image.width, image.height), (8, 8));
+        assert_eq!(image.get(0, 0), UNCLAIMED);
+        assert_eq!(image.get(1, 3), CLAIMED);
+        assert_eq!(image.get(5, 5), INTACT);
+        assert_eq!(image.get(3, 3), CONTESTED.0);
+        // the only square inch under three claims
+        assert_eq!(image.get(4, 4), CONTESTED.1);
+    }
+
+    #[test]
+    fn too_big() {
+        let claims = parse_claims("#1 @ 0,0: 100000x100000").unwrap();
+        assert!(heatmap(&claims).is_err());
+    }
+}
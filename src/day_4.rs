@@ -1,30 +1,47 @@
-#![feature(slice_patterns)]
-
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
-type StdResult<T> = Result<T, Box<std::error::Error>>;
+use crate::error::{parse_lines, LineError, ParseError};
+use crate::parser::{whole, Parser};
+use crate::{Solution, StdResult};
+
+pub struct Day4;
 
-fn main() -> StdResult<()> {
-    let input = include_str!("../input/day_4_input.txt");
+impl Solution for Day4 {
+    type Input = Vec<LogLine>;
+    type PartOne = i32;
+    type PartTwo = i32;
 
-    let solution_one = part_one(input)?;
-    println!("Part One: {}", solution_one);
+    fn parse(&self, input: &str) -> StdResult<Vec<LogLine>> {
+        Ok(parse_log_lines(input)?)
+    }
 
-    let solution_two = part_two(input)?;
-    println!("Part Two: {}", solution_two);
+    fn part_one(&self, lines: &Vec<LogLine>) -> StdResult<i32> {
+        part_one(lines)
+    }
 
-    Ok(())
+    fn part_two(&self, lines: &Vec<LogLine>) -> StdResult<i32> {
+        part_two(lines)
+    }
 }
 
-fn parse_log_lines(input: &str) -> StdResult<Vec<LogLine>> {
-    let mut lines = input
-        .lines()
-        .map(LogLine::parse)
-        .collect::<StdResult<Vec<LogLine>>>()?;
-    lines.sort_by(|x, y| x.timestamp().cmp(y.timestamp()));
+pub fn parse_log_lines(input: &str) -> Result<Vec<LogLine>, ParseError> {
+    let mut lines: Vec<(usize, LogLine)> = parse_lines(4, input, LogLine::parse)?
+        .into_iter()
+        .enumerate()
+        .collect();
+    lines.sort_by(|(_, x), (_, y)| x.timestamp().cmp(y.timestamp()));
+
+    // nobody can fall asleep or wake up before the first shift starts
+    if let Some((i, line)) = lines.first() {
+        if !matches!(line, LogLine::Guard(..)) {
+            let text = input.lines().nth(*i).unwrap_or("");
+            let event = text.find("] ").map_or(0, |end| end + 2);
+            Err(LineError::new(event, "'Guard #' before any other event").at(4, i + 1, text))?
+        }
+    }
 
-    Ok(lines)
+    Ok(lines.into_iter().map(|(_, line)| line).collect())
 }
 
 #[derive(Clone, Debug)]
@@ -68,7 +85,7 @@ fn sleep_map(data: Vec<NightData>) -> StdResult<HashMap<i32, HashMap<i32, i32>>>
     let mut result = HashMap::new();
     for datum in &data {
         let (guard_id, minutes) = sleep_map_entry(datum)?;
-        let minutes_map = result.entry(guard_id).or_insert_with(|| HashMap::new());
+        let minutes_map = result.entry(guard_id).or_insert_with(HashMap::new);
         for m in &minutes {
             let minutes_slept = (*minutes_map).entry(*m).or_insert(0);
             *minutes_slept += 1;
@@ -78,10 +95,10 @@ fn sleep_map(data: Vec<NightData>) -> StdResult<HashMap<i32, HashMap<i32, i32>>>
     Ok(result)
 }
 
-fn to_night_data(lines: Vec<LogLine>) -> Vec<NightData> {
+fn to_night_data(lines: &[LogLine]) -> Vec<NightData> {
     let mut result = vec![];
     let mut current_data: Option<NightData> = None;
-    for line in &lines {
+    for line in lines {
         match line {
             LogLine::Guard(id, _ts) => {
                 if let Some(data) = current_data {
@@ -113,8 +130,7 @@ fn to_night_data(lines: Vec<LogLine>) -> Vec<NightData> {
     result
 }
 
-fn part_one(input: &str) -> StdResult<i32> {
-    let lines = parse_log_lines(input)?;
+pub fn part_one(lines: &[LogLine]) -> StdResult<i32> {
     let sleep_map = sleep_map(to_night_data(lines))?;
 
     let guard_id = sleepiest_guard(&sleep_map)?;
@@ -141,7 +157,7 @@ fn sleepiest_guard(sleep_map: &HashMap<i32, HashMap<i32, i32>>) -> StdResult<i32
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct TimeStamp {
+pub struct TimeStamp {
     year: i32,
     month: i32,
     day: i32,
@@ -150,64 +166,60 @@ struct TimeStamp {
 }
 
 impl TimeStamp {
-    fn parse(day_chunk: &str, time_chunk: &str) -> StdResult<TimeStamp> {
-        let mut timestamp = TimeStamp::default();
-
-        let mut day_chunk: String = day_chunk.to_string();
-        day_chunk.remove(0);
-        let day_v: Vec<&str> = day_chunk.split("-").collect();
-        match day_v.as_slice() {
-            [y, m, d] => {
-                timestamp.year = y.parse()?;
-                timestamp.month = m.parse()?;
-                timestamp.day = d.parse()?;
-            }
-            _ => Err("parse date failed")?,
-        }
-
-        let mut time_chunk = time_chunk.to_string();
-        time_chunk.pop();
-        let time_v: Vec<&str> = time_chunk.split(":").collect();
-        match time_v.as_slice() {
-            [h, m] => {
-                timestamp.hour = h.parse()?;
-                timestamp.minute = m.parse()?;
-            }
-            _ => Err("parse time failed")?,
-        }
+    // eg "[1518-11-01 00:00]"
+    pub fn parse(stamp: &str) -> Result<TimeStamp, LineError> {
+        whole(stamp, TimeStamp::parse_from)
+    }
 
-        Ok(timestamp)
+    fn parse_from(p: &mut Parser) -> Result<TimeStamp, LineError> {
+        p.bracketed("[", "]", |p| {
+            let year = p.integer()?;
+            p.literal("-")?;
+            let month = p.integer()?;
+            p.literal("-")?;
+            let day = p.integer()?;
+            p.literal(" ")?;
+            let hour = p.integer()?;
+            p.literal(":")?;
+            let minute = p.integer()?;
+
+            Ok(TimeStamp {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+            })
+        })
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum LogLine {
+pub enum LogLine {
     Guard(i32, TimeStamp),
     Fall(TimeStamp),
     Wake(TimeStamp),
 }
 
 impl LogLine {
-    fn parse(line: &str) -> StdResult<LogLine> {
-        let v: Vec<&str> = line.split_whitespace().collect();
-        match v.as_slice() {
-            [d, t, w, i, _rest..] => {
-                let ts = TimeStamp::parse(d, t)?;
-                if w.starts_with('G') {
-                    let mut i = i.to_string();
-                    i.remove(0);
-                    let id: i32 = i.parse()?;
-                    Ok(LogLine::Guard(id, ts))
-                } else if w.starts_with('f') {
-                    Ok(LogLine::Fall(ts))
-                } else if w.starts_with('w') {
-                    Ok(LogLine::Wake(ts))
-                } else {
-                    Err("parse log line failed")?
-                }
+    // eg "[1518-11-01 00:00] Guard #10 begins shift"
+    pub fn parse(line: &str) -> Result<LogLine, LineError> {
+        whole(line, |p| {
+            let ts = TimeStamp::parse_from(p)?;
+            p.literal(" ")?;
+
+            if p.eat("Guard #") {
+                let id = p.integer()?;
+                p.literal(" begins shift")?;
+                Ok(LogLine::Guard(id, ts))
+            } else if p.eat("falls asleep") {
+                Ok(LogLine::Fall(ts))
+            } else if p.eat("wakes up") {
+                Ok(LogLine::Wake(ts))
+            } else {
+                Err(p.error("'Guard #', 'falls asleep' or 'wakes up'"))
             }
-            _ => Err("parse log line failed")?,
-        }
+        })
     }
 
     fn timestamp(&self) -> &TimeStamp {
@@ -247,14 +259,13 @@ impl Ord for TimeStamp {
     }
 }
 
-fn part_two(input: &str) -> StdResult<i32> {
-    let lines = parse_log_lines(input)?;
+pub fn part_two(lines: &[LogLine]) -> StdResult<i32> {
     let data = to_night_data(lines);
     let sleep_map = sleep_map(data)?;
-    Ok(solve_part_two(sleep_map))
+    solve_part_two(sleep_map)
 }
 
-fn solve_part_two(sleep_map: HashMap<i32, HashMap<i32, i32>>) -> i32 {
+fn solve_part_two(sleep_map: HashMap<i32, HashMap<i32, i32>>) -> StdResult<i32> {
     let (guard_id, minute_of_hour, _minutes_slept) = sleep_map
         .iter()
         .map(|(&guard_id, minutes)| {
@@ -264,10 +275,10 @@ fn solve_part_two(sleep_map: HashMap<i32, HashMap<i32, i32>>) -> i32 {
                 .map(|(&m, &c)| (guard_id, m, c))
                 .unwrap_or((guard_id, 0, 0))
         })
-        .max_by(|(_, _, l_total), (_, _, r_total)| l_total.cmp(&r_total))
-        .unwrap();
+        .max_by(|(_, _, l_total), (_, _, r_total)| l_total.cmp(r_total))
+        .ok_or("empty data")?;
 
-    guard_id * minute_of_hour
+    Ok(guard_id * minute_of_hour)
 }
 
 #[cfg(test)]
@@ -279,11 +290,7 @@ mod tests {
     #[test]
     fn parse_timestamp() {
         let input = "[1518-11-01 00:00]";
-        let v: Vec<&str> = input.split_whitespace().collect();
-        let timestamp = match v.as_slice() {
-            [day_chunk, time_chunk] => TimeStamp::parse(day_chunk, time_chunk).unwrap(),
-            _ => panic!(),
-        };
+        let timestamp = TimeStamp::parse(input).unwrap();
 
         assert_eq!(timestamp.year, 1518);
         assert_eq!(timestamp.month, 11);
@@ -308,29 +315,52 @@ mod tests {
         assert_eq!(ts.minute, 58);
     }
 
+    #[test]
+    fn parse_error_position() {
+        let input = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:x5] falls asleep";
+        let error = parse_log_lines(input).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 16);
+        assert_eq!(error.expected, "a number");
+
+        // events out of order in the file, but the earliest has no guard
+        let input = "[1518-11-01 00:30] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep";
+        let error = parse_log_lines(input).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 20);
+        assert_eq!(error.expected, "'Guard #' before any other event");
+
+        let lines = parse_log_lines("").unwrap();
+        assert!(part_one(&lines).is_err());
+        assert!(part_two(&lines).is_err());
+    }
+
     #[test]
     fn parse_lines() {
         let lines = parse_log_lines(EXAMPLE_INPUT).unwrap();
-        let data = to_night_data(lines);
+        let data = to_night_data(&lines);
         assert_eq!(data.len(), 5);
     }
 
     #[test]
     fn part_1_example() {
-        let result = part_one(EXAMPLE_INPUT).unwrap();
+        let lines = parse_log_lines(EXAMPLE_INPUT).unwrap();
+        let result = part_one(&lines).unwrap();
         assert_eq!(240, result);
     }
 
     #[test]
     fn part_2_example() {
-        let result = part_two(EXAMPLE_INPUT).unwrap();
+        let lines = parse_log_lines(EXAMPLE_INPUT).unwrap();
+        let result = part_two(&lines).unwrap();
         assert_eq!(4455, result);
     }
 
     #[test]
     fn part_2_for_real() {
-        let input = include_str!("../input/day_4_input.txt");
-        let solution_two = part_two(input).unwrap();
+        let input = include_str!("input/day_4_input.txt");
+        let lines = parse_log_lines(input).unwrap();
+        let solution_two = part_two(&lines).unwrap();
 
         assert_eq!(solution_two, 22687);
     }
@@ -1,28 +1,39 @@
-use std::str;
+use crate::error::ParseError;
+use crate::parser::whole;
+use crate::{Solution, StdResult};
 
-type StdResult<T> = Result<T, Box<std::error::Error>>;
+pub struct Day5;
 
-fn main() -> StdResult<()> {
-    let input = include_str!("../input/day_5_input.txt").trim();
+impl Solution for Day5 {
+    type Input = Vec<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let solution_one = part_one(input)?;
-    println!("Part One: {}", solution_one);
+    fn parse(&self, input: &str) -> StdResult<Vec<u8>> {
+        let polymer = whole(input.trim_end(), |p| {
+            p.take_while1(|c| c.is_ascii_alphabetic(), "a unit")
+        })
+        .map_err(|e| ParseError::at_offset(5, input, e.offset, &e.expected))?;
 
-    let solution_two = part_two(input);
-    println!("Part Two: {}", solution_two);
+        Ok(polymer.as_bytes().to_vec())
+    }
 
-    Ok(())
+    fn part_one(&self, polymer: &Vec<u8>) -> StdResult<usize> {
+        Ok(part_one(polymer))
+    }
+
+    fn part_two(&self, polymer: &Vec<u8>) -> StdResult<usize> {
+        Ok(part_two(polymer))
+    }
 }
 
-fn part_one(input: &str) -> StdResult<usize> {
-    let bytes = input.as_bytes().to_vec();
-    let result = react(bytes);
+pub fn part_one(polymer: &[u8]) -> usize {
+    let result = react(polymer.to_vec());
 
-    Ok(result.len())
+    result.len()
 }
 
-fn part_two(input: &str) -> usize {
-    let input = input.as_bytes().to_vec();
+pub fn part_two(input: &[u8]) -> usize {
     let mut results = vec![];
     for i in 0..26 {
         let a = b'a' + i;
@@ -30,7 +41,7 @@ fn part_two(input: &str) -> usize {
         let v = input
             .iter()
             .filter(|&&c| c != a && c != b)
-            .map(|b| *b)
+            .copied()
             .collect();
         let v = react(v);
         results.push(v.len());
@@ -39,7 +50,7 @@ fn part_two(input: &str) -> usize {
     *results.iter().min().unwrap()
 }
 
-fn react(mut bytes: Vec<u8>) -> Vec<u8> {
+pub fn react(mut bytes: Vec<u8>) -> Vec<u8> {
     while let Some(i) = find_reaction(&bytes) {
         bytes.remove(i);
         bytes.remove(i);
@@ -59,7 +70,7 @@ fn find_reaction(bytes: &[u8]) -> Option<usize> {
 }
 
 fn should_react(a: u8, b: u8) -> bool {
-    a != b && a.to_ascii_lowercase() == b.to_ascii_lowercase()
+    a != b && a.eq_ignore_ascii_case(&b)
 }
 
 #[cfg(test)]
@@ -70,7 +81,7 @@ mod tests {
 
     #[test]
     fn part_one_example() {
-        let result = part_one(EXAMPLE_INPUT).unwrap();
+        let result = part_one(EXAMPLE_INPUT.as_bytes());
         assert_eq!(result, 10);
     }
 
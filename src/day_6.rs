@@ -1,40 +1,70 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-type StdResult<T> = Result<T, Box<std::error::Error>>;
+use crate::error::{parse_lines, LineError, ParseError};
+use crate::params::Params;
+use crate::parser::whole;
+use crate::{Solution, StdResult};
 
-type Coord = (i32, i32);
+pub type Coord = (i32, i32);
 
 // The usize is a region id for what the coordinate is closest to
 type Grid = HashMap<Coord, Option<usize>>;
 
-fn main() -> StdResult<()> {
-    let input = include_str!("../input/day_6_input.txt").trim();
+pub struct Day6 {
+    // part two's region is every point with a total distance under this
+    pub threshold: usize,
+}
 
-    let solution_one = part_one(input)?;
-    println!("Part One: {}", solution_one);
+impl Default for Day6 {
+    fn default() -> Day6 {
+        Day6 { threshold: 10_000 }
+    }
+}
 
-    let solution_two = part_two(input)?;
-    println!("Part Two: {}", solution_two);
+impl Day6 {
+    pub fn from_params(params: &Params) -> StdResult<Day6> {
+        params.expect_only(&["threshold"])?;
+        let default = Day6::default();
 
-    Ok(())
+        Ok(Day6 {
+            threshold: params.get("threshold")?.unwrap_or(default.threshold),
+        })
+    }
 }
 
-fn part_two(input: &str) -> StdResult<usize> {
-    let coords = parse_coordinates(input)?;
+impl Solution for Day6 {
+    type Input = Vec<Coord>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> StdResult<Vec<Coord>> {
+        Ok(parse_coordinates(input.trim_end())?)
+    }
+
+    fn part_one(&self, coords: &Vec<Coord>) -> StdResult<usize> {
+        part_one(coords)
+    }
+
+    fn part_two(&self, coords: &Vec<Coord>) -> StdResult<usize> {
+        part_two(coords, self.threshold)
+    }
+}
+
+pub fn part_two(coords: &[Coord], threshold: usize) -> StdResult<usize> {
     let grid = count_distances(coords)?;
-    let sol = grid.values().filter(|&&count| count < 10_000).count();
+    let sol = grid.values().filter(|&&count| count < threshold).count();
     Ok(sol)
 }
 
-fn count_distances(centers: Vec<Coord>) -> StdResult<HashMap<Coord, usize>> {
-    let (x_max, y_max) = maximums(&centers)?;
+pub fn count_distances(centers: &[Coord]) -> StdResult<HashMap<Coord, usize>> {
+    let (x_max, y_max) = maximums(centers)?;
     let mut result = HashMap::new();
 
     for x in 0..=x_max {
         for y in 0..=y_max {
             let coordinate = (x, y);
-            for center in &centers {
+            for center in centers {
                 let distance = manhattan(coordinate, *center);
                 let total = result.entry(coordinate).or_insert(0);
                 *total += distance;
@@ -49,10 +79,9 @@ fn manhattan(a: Coord, b: Coord) -> usize {
     ((a.0 - b.0).abs() + (a.1 - b.1).abs()) as usize
 }
 
-fn part_one(input: &str) -> StdResult<usize> {
-    let coords = parse_coordinates(input)?;
+pub fn part_one(coords: &[Coord]) -> StdResult<usize> {
     let num_regions = coords.len();
-    let maxes = maximums(&coords)?;
+    let maxes = maximums(coords)?;
 
     let mut grid = grid(coords);
     let mut done = false;
@@ -110,9 +139,8 @@ fn count_grid(grid: &Grid, region_id: usize) -> usize {
         .count()
 }
 
-fn djikstra_round(mut grid: Grid, maxes: Coord) -> (bool, Grid) {
+pub fn djikstra_round(mut grid: Grid, maxes: Coord) -> (bool, Grid) {
     let (x_max, y_max) = maxes;
-    let mut done = true;
 
     // usize here is group id; None = tie between regions
     // do we care about what regions they are? or their distance? not for part one
@@ -126,16 +154,14 @@ fn djikstra_round(mut grid: Grid, maxes: Coord) -> (bool, Grid) {
                 .filter_map(|region| *region)
                 .collect();
 
-            if neighboring_regions.is_empty() {
-                done = false;
-            } else {
-                if let None = grid.get(&(x, y)) {
-                    marks.insert((x, y), neighboring_regions);
-                }
+            if !neighboring_regions.is_empty() && !grid.contains_key(&(x, y)) {
+                marks.insert((x, y), neighboring_regions);
             }
         }
     }
 
+    // cells walled in by ties are never claimed, so stop once nothing changes
+    let done = marks.is_empty();
     for (coord, regions) in marks {
         if regions.len() > 1 {
             grid.insert(coord, None);
@@ -149,25 +175,26 @@ fn djikstra_round(mut grid: Grid, maxes: Coord) -> (bool, Grid) {
     (done, grid)
 }
 
-fn parse_coordinates(input: &str) -> StdResult<Vec<Coord>> {
-    input.lines().map(parse_line).collect()
+pub fn parse_coordinates(input: &str) -> Result<Vec<Coord>, ParseError> {
+    parse_lines(6, input, parse_line)
 }
 
-fn parse_line(line: &str) -> StdResult<Coord> {
-    let s: Vec<_> = line.split(", ").collect();
-    match s.as_slice() {
-        [x, y] => Ok((x.parse()?, y.parse()?)),
-        _ => Err("parsing failed")?,
-    }
+// eg "1, 6"
+fn parse_line(line: &str) -> Result<Coord, LineError> {
+    whole(line, |p| {
+        let x = p.integer()?;
+        p.literal(", ")?;
+        Ok((x, p.integer()?))
+    })
 }
 
-fn maximums(v: &Vec<Coord>) -> StdResult<Coord> {
+fn maximums(v: &[Coord]) -> StdResult<Coord> {
     let &x = v.iter().map(|(x, _)| x).max().ok_or("empty data")?;
     let &y = v.iter().map(|(_, y)| y).max().ok_or("empty data")?;
     Ok((x, y))
 }
 
-fn grid(coordinates: Vec<Coord>) -> Grid {
+fn grid(coordinates: &[Coord]) -> Grid {
     coordinates
         .iter()
         .enumerate()
@@ -188,14 +215,33 @@ mod tests {
 
     #[test]
     fn part_one_example() {
-        let result = part_one(EXAMPLE_INPUT).unwrap();
+        let coords = parse_coordinates(EXAMPLE_INPUT).unwrap();
+        let result = part_one(&coords).unwrap();
         assert_eq!(result, 17)
     }
 
+    #[test]
+    fn walled_in_by_ties() {
+        assert_eq!(part_one(&[(0, 0), (0, 2)]).unwrap(), 0);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let coords = parse_coordinates(EXAMPLE_INPUT).unwrap();
+        let result = part_two(&coords, 32).unwrap();
+        assert_eq!(result, 16)
+    }
+
     #[test]
     fn parsing() {
         let v = parse_coordinates(EXAMPLE_INPUT).unwrap();
         assert_eq!(v[0], (1, 1))
     }
 
+    #[test]
+    fn parse_error_position() {
+        let error = parse_coordinates("1, 1\n1, 6\n8, three").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 4);
+    }
 }
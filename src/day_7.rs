@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::error::{parse_lines, LineError, ParseError};
+use crate::params::Params;
+use crate::parser::{whole, Parser};
+use crate::{Solution, StdResult};
+
+pub struct Day7 {
+    // each step takes this long plus its letter's position in the alphabet
+    pub base_seconds: u32,
+    pub workers: usize,
+}
+
+impl Default for Day7 {
+    fn default() -> Day7 {
+        Day7 {
+            base_seconds: 60,
+            workers: 5,
+        }
+    }
+}
+
+impl Day7 {
+    pub fn from_params(params: &Params) -> StdResult<Day7> {
+        params.expect_only(&["base_seconds", "workers"])?;
+        let default = Day7::default();
+
+        let workers = params.get("workers")?.unwrap_or(default.workers);
+        if workers == 0 {
+            Err("day 7 needs at least one worker")?
+        }
+
+        Ok(Day7 {
+            base_seconds: params.get("base_seconds")?.unwrap_or(default.base_seconds),
+            workers,
+        })
+    }
+}
+
+impl Solution for Day7 {
+    type Input = Dependencies;
+    type PartOne = String;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> StdResult<Dependencies> {
+        Ok(parse_dependencies(input.trim_end())?)
+    }
+
+    fn part_one(&self, deps_map: &Dependencies) -> StdResult<String> {
+        part_one(deps_map, &all_tasks(deps_map))
+    }
+
+    fn part_two(&self, deps_map: &Dependencies) -> StdResult<usize> {
+        part_two(
+            deps_map,
+            &all_tasks(deps_map),
+            self.base_seconds,
+            self.workers,
+        )
+    }
+}
+
+// every step named in the instructions
+fn all_tasks(deps_map: &Dependencies) -> HashSet<u8> {
+    deps_map
+        .iter()
+        .flat_map(|(task, deps)| deps.iter().chain(Some(task)))
+        .copied()
+        .collect()
+}
+
+pub type Dependencies = HashMap<u8, HashSet<u8>>;
+
+pub fn part_one(deps_map: &Dependencies, tasks: &HashSet<u8>) -> StdResult<String> {
+    let mut result = "".to_string();
+
+    let mut completed_tasks: HashSet<u8> = HashSet::new();
+    while completed_tasks.len() < tasks.len() {
+        let available_tasks = available_tasks(tasks, deps_map, &completed_tasks);
+
+        let &next = available_tasks.iter().min().expect("no available tasks");
+        result.push(next as char);
+        completed_tasks.insert(next);
+    }
+
+    Ok(result)
+}
+
+pub fn part_two(
+    deps_map: &Dependencies,
+    tasks: &HashSet<u8>,
+    wait: u32,
+    workers: usize,
+) -> StdResult<usize> {
+    let mut time_taken = 0;
+    let mut workers: Vec<Option<u8>> = vec![None; workers];
+    let mut completed_tasks: HashSet<u8> = HashSet::new();
+    let mut work_remaining: HashMap<u8, u64> = (b'A'..=b'Z')
+        .enumerate()
+        .map(|(i, task)| (task, u64::from(wait) + 1 + i as u64))
+        .collect();
+
+    while completed_tasks != *tasks {
+        time_taken += 1;
+        let available_tasks = available_tasks(tasks, deps_map, &completed_tasks);
+        workers = assign_workers(workers, available_tasks);
+
+        for worker in workers.iter_mut() {
+            if let Some(task) = *worker {
+                let time = work_remaining.entry(task).or_insert_with(|| panic!("agh"));
+                *time -= 1;
+
+                if *time == 0 {
+                    *worker = None;
+                    completed_tasks.insert(task);
+                }
+            }
+        }
+    }
+
+    Ok(time_taken)
+}
+
+fn assign_workers(mut workers: Vec<Option<u8>>, available_tasks: HashSet<u8>) -> Vec<Option<u8>> {
+    let worked_tasks: HashSet<_> = workers.iter().filter_map(|task| *task).collect();
+    let mut available_tasks: Vec<_> = available_tasks.difference(&worked_tasks).collect();
+    // idle workers take steps in alphabetical order, so pop from the back
+    available_tasks.sort_unstable_by(|a, b| b.cmp(a));
+
+    for worker in workers.iter_mut().filter(|worker| worker.is_none()) {
+        if let Some(&task) = available_tasks.pop() {
+            *worker = Some(task);
+        }
+    }
+
+    workers
+}
+
+fn available_tasks(
+    tasks: &HashSet<u8>,
+    deps_map: &Dependencies,
+    completed_tasks: &HashSet<u8>,
+) -> HashSet<u8> {
+    let available = |task: &&u8| {
+        !completed_tasks.contains(task)
+            && deps_map
+                .get(task)
+                .is_none_or(|deps| deps.iter().all(|dep| completed_tasks.contains(dep)))
+    };
+
+    tasks.iter().filter(available).copied().collect()
+}
+
+pub fn parse_dependencies(input: &str) -> Result<Dependencies, ParseError> {
+    let mut deps_map = HashMap::new();
+    for (task, prereq) in parse_lines(7, input, parse_line)? {
+        let deps = deps_map.entry(task).or_insert_with(HashSet::new);
+        deps.insert(prereq);
+    }
+
+    Ok(deps_map)
+}
+
+// eg "Step C must be finished before step A can begin."
+fn parse_line(line: &str) -> Result<(u8, u8), LineError> {
+    whole(line, |p| {
+        p.literal("Step ")?;
+        let prerequisite = step(p)?;
+        p.literal(" must be finished before step ")?;
+        let task = step(p)?;
+        p.literal(" can begin.")?;
+        Ok((task, prerequisite))
+    })
+}
+
+fn step(p: &mut Parser) -> Result<u8, LineError> {
+    let step = p.char_where(|c| c.is_ascii_uppercase(), "a step letter")?;
+    Ok(step as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Step C must be finished before step A can begin.\nStep C must be finished before step F can begin.\nStep A must be finished before step B can begin.\nStep A must be finished before step D can begin.\nStep B must be finished before step E can begin.\nStep D must be finished before step E can begin.\nStep F must be finished before step E can begin.";
+
+    #[test]
+    fn parsing() {
+        let deps = parse_dependencies(EXAMPLE_INPUT).unwrap();
+        assert_eq!(deps.len(), 5);
+        assert!(deps.get(&b'A').unwrap().contains(&b'C'));
+        assert!(deps.get(&b'F').unwrap().contains(&b'C'));
+        assert!(deps.get(&b'B').unwrap().contains(&b'A'));
+        assert!(deps.get(&b'D').unwrap().contains(&b'A'));
+        assert!(deps.get(&b'E').unwrap().contains(&b'B'));
+        assert!(deps.get(&b'E').unwrap().contains(&b'D'));
+        assert!(deps.get(&b'E').unwrap().contains(&b'F'));
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = parse_dependencies(
+            "Step C must be finished before step A can begin.\nStep C must be done",
+        )
+        .unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 7);
+        assert_eq!(error.expected, "' must be finished before step '");
+    }
+
+    #[test]
+    fn part_one_example() {
+        let tasks: HashSet<u8> = (b'A'..=b'F').collect();
+        let deps = parse_dependencies(EXAMPLE_INPUT).unwrap();
+        let result = part_one(&deps, &tasks).unwrap();
+        assert_eq!(result, "CABDFE".to_string());
+    }
+
+    #[test]
+    fn example_params() {
+        let mut params = Params::new(7);
+        params.set("base_seconds", "0");
+        params.set("workers", "2");
+        let day = Day7::from_params(&params).unwrap();
+
+        let deps = day.parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(day.part_one(&deps).unwrap(), "CABDFE");
+        assert_eq!(day.part_two(&deps).unwrap(), 15);
+
+        params.set("base_seconds", "-70");
+        assert!(Day7::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn part_two_example() {
+        let tasks: HashSet<u8> = (b'A'..=b'F').collect();
+        let deps = parse_dependencies(EXAMPLE_INPUT).unwrap();
+        let result = part_two(&deps, &tasks, 0, 2).unwrap();
+        assert_eq!(result, 15);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::error::{LineError, ParseError};
+use crate::parser::Parser;
+use crate::{Solution, StdResult};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Node;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> StdResult<Node> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(&self, node: &Node) -> StdResult<usize> {
+        Ok(sum_metadata(node))
+    }
+
+    fn part_two(&self, node: &Node) -> StdResult<usize> {
+        Ok(value(node))
+    }
+}
+
+pub fn sum_metadata(node: &Node) -> usize {
+    let mut sum = node.metadata.iter().sum();
+    for child in &node.children {
+        sum += sum_metadata(child);
+    }
+
+    sum
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub metadata: Vec<usize>,
+    pub children: Vec<Node>,
+}
+
+pub fn parse_input(input: &str) -> Result<Node, ParseError> {
+    let (nums, offsets) = parse_numbers(input)?;
+    let (node, remaining) = parse_node(nums).ok_or_else(|| {
+        // the tree wants more numbers than there are
+        ParseError::at_offset(8, input, input.trim_end().len(), "a number")
+    })?;
+
+    if !remaining.is_empty() {
+        let extra = offsets[offsets.len() - remaining.len()];
+        Err(ParseError::at_offset(8, input, extra, "end of input"))?
+    }
+
+    Ok(node)
+}
+
+// Whitespace separated numbers, and where each one starts
+fn parse_numbers(input: &str) -> Result<(Vec<usize>, Vec<usize>), ParseError> {
+    let located = |e: LineError| ParseError::at_offset(8, input, e.offset, &e.expected);
+
+    let mut p = Parser::new(input);
+    let mut nums = vec![];
+    let mut offsets = vec![];
+    p.skip_whitespace();
+    while !p.is_done() {
+        offsets.push(p.offset());
+        nums.push(p.integer().map_err(located)?);
+        if !p.is_done() {
+            p.take_while1(char::is_whitespace, "a space")
+                .map_err(located)?;
+        }
+    }
+
+    Ok((nums, offsets))
+}
+
+// None if the numbers run out partway through the node
+pub fn parse_node(mut v: Vec<usize>) -> Option<(Node, Vec<usize>)> {
+    if v.len() < 2 {
+        return None;
+    }
+    let child_count = v[0];
+    let meta_count = v[1];
+    v.drain(0..2);
+
+    let mut children = vec![];
+    for _child in 0..child_count {
+        let (child, remaining) = parse_node(v)?;
+        v = remaining;
+        children.push(child);
+    }
+
+    if v.len() < meta_count {
+        return None;
+    }
+    let metadata: Vec<_> = v.drain(0..meta_count).collect();
+
+    let node = Node { metadata, children };
+
+    Some((node, v))
+}
+
+pub fn value(node: &Node) -> usize {
+    if node.children.is_empty() {
+        return node.metadata.iter().sum();
+    }
+
+    let indicies: Vec<_> = node
+        .metadata
+        .iter()
+        .map(|i| i - 1)
+        .filter(|&i| i < node.children.len())
+        .collect();
+
+    let mut val = 0;
+    for index in indicies {
+        val += value(&node.children[index])
+    }
+
+    val
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn part_one_test() {
+        let node = parse_input(EXAMPLE_INPUT).unwrap();
+        let result = sum_metadata(&node);
+        assert_eq!(result, 138);
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = parse_input("0 1 5 0 1").unwrap_err();
+        assert_eq!(error.column, 7);
+        assert_eq!(error.expected, "end of input");
+
+        for truncated in &["", "\n", "2 3", "1 1 0 2 5\n"] {
+            let error = parse_input(truncated).unwrap_err();
+            assert_eq!(error.line, 1);
+            assert_eq!(error.column, truncated.trim_end().len() + 1);
+            assert_eq!(error.expected, "a number");
+        }
+    }
+
+    #[test]
+    fn part_two_test() {
+        let node = parse_input(EXAMPLE_INPUT).unwrap();
+        let result = value(&node);
+        assert_eq!(result, 66);
+    }
+}
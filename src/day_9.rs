@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::error::LineError;
+use crate::params::Params;
+use crate::parser::whole;
+use crate::{Solution, StdResult};
+
+pub struct Day9 {
+    // override the game described by the input
+    pub players: Option<usize>,
+    pub last_marble: Option<usize>,
+    // part two's last marble is this many times larger
+    pub multiplier: usize,
+}
+
+impl Default for Day9 {
+    fn default() -> Day9 {
+        Day9 {
+            players: None,
+            last_marble: None,
+            multiplier: 100,
+        }
+    }
+}
+
+impl Day9 {
+    pub fn from_params(params: &Params) -> StdResult<Day9> {
+        params.expect_only(&["players", "last_marble", "multiplier"])?;
+        let default = Day9::default();
+
+        let players = params.get("players")?;
+        if players == Some(0) {
+            Err("day 9 needs at least one player")?
+        }
+
+        let last_marble = params.get("last_marble")?;
+        if last_marble == Some(0) {
+            Err("day 9 needs a last marble of at least 1")?
+        }
+
+        let multiplier = params.get("multiplier")?.unwrap_or(default.multiplier);
+        if multiplier == 0 {
+            Err("day 9 needs a multiplier of at least 1")?
+        }
+
+        Ok(Day9 {
+            players,
+            last_marble,
+            multiplier,
+        })
+    }
+
+    fn game(&self, game: &Game) -> Game {
+        Game {
+            players: self.players.unwrap_or(game.players),
+            last_marble: self.last_marble.unwrap_or(game.last_marble),
+        }
+    }
+}
+
+impl Solution for Day9 {
+    type Input = Game;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> StdResult<Game> {
+        let line = input.trim_end();
+        Ok(Game::parse(line).map_err(|e| e.at(9, 1, line))?)
+    }
+
+    fn part_one(&self, game: &Game) -> StdResult<usize> {
+        let game = self.game(game);
+        solve(game.players, game.last_marble)
+    }
+
+    fn part_two(&self, game: &Game) -> StdResult<usize> {
+        let game = self.game(game);
+        let last_marble = game
+            .last_marble
+            .checked_mul(self.multiplier)
+            .ok_or("part two's last marble is too large")?;
+        solve(game.players, last_marble)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    pub players: usize,
+    pub last_marble: usize,
+}
+
+impl Game {
+    // eg "400 players; last marble is worth 71864 points"
+    pub fn parse(line: &str) -> Result<Game, LineError> {
+        whole(line, |p| {
+            let start = p.offset();
+            let players = p.integer()?;
+            if players == 0 {
+                Err(LineError::new(start, "at least one player"))?
+            }
+            p.literal(" players; last marble is worth ")?;
+            let marble_start = p.offset();
+            let last_marble = p.integer()?;
+            if last_marble == 0 {
+                Err(LineError::new(marble_start, "at least one marble"))?
+            }
+            p.literal(" points")?;
+            Ok(Game {
+                players,
+                last_marble,
+            })
+        })
+    }
+}
+
+struct Turn {
+    circle: VecDeque<usize>,
+    marble: usize,
+}
+
+impl Turn {
+    fn new(circle: VecDeque<usize>, marble: usize) -> Turn {
+        Turn { circle, marble }
+    }
+}
+
+struct TurnResult {
+    circle: VecDeque<usize>,
+    score: usize,
+}
+
+impl TurnResult {
+    fn new(circle: VecDeque<usize>, score: usize) -> TurnResult {
+        TurnResult { circle, score }
+    }
+}
+
+pub fn solve(players: usize, last_marble: usize) -> StdResult<usize> {
+    if players == 0 {
+        Err("a game needs at least one player")?
+    }
+
+    let mut circle = VecDeque::new();
+    circle.push_front(0);
+    let mut scores = HashMap::new();
+
+    for turn in 1..=last_marble {
+        let result = add_marble(Turn::new(circle, turn));
+        circle = result.circle;
+        let player_score = scores.entry(turn % players).or_insert(0);
+        *player_score += result.score;
+    }
+
+    // nobody scores without marbles
+    Ok(scores.values().max().copied().unwrap_or(0))
+}
+
+fn add_marble(turn: Turn) -> TurnResult {
+    let Turn { mut circle, marble } = turn;
+
+    if marble % 23 == 0 {
+        let mut score = marble;
+        circle.rotate_right(7);
+        score += circle.pop_front().unwrap();
+        return TurnResult::new(circle, score);
+    }
+
+    circle.rotate_left(1);
+    circle.rotate_left(1);
+    circle.push_front(turn.marble);
+    TurnResult::new(circle, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let result = solve(9, 25).unwrap();
+        assert_eq!(result, 32);
+
+        let result = solve(10, 1618).unwrap();
+        assert_eq!(result, 8317);
+
+        let result = solve(13, 7999).unwrap();
+        assert_eq!(result, 146373);
+    }
+
+    #[test]
+    fn parsing() {
+        let game = Game::parse("10 players; last marble is worth 1618 points").unwrap();
+        assert_eq!(game.players, 10);
+        assert_eq!(game.last_marble, 1618);
+
+        let error = Game::parse("0 players; last marble is worth 1618 points").unwrap_err();
+        assert_eq!(error.offset, 0);
+        let error = Game::parse("10 players; last marble is worth 0 points").unwrap_err();
+        assert_eq!(error.offset, 33);
+        assert_eq!(error.expected, "at least one marble");
+    }
+
+    #[test]
+    fn degenerate_games() {
+        assert_eq!(solve(3, 0).unwrap(), 0);
+        assert!(solve(0, 25).is_err());
+
+        for bad in &["last_marble=0", "multiplier=0", "players=0"] {
+            let mut params = Params::new(9);
+            params.set_arg(bad).unwrap();
+            assert!(Day9::from_params(&params).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn params_override_input() {
+        let mut params = Params::new(9);
+        params.set("players", "9");
+        params.set("last_marble", "25");
+        let day = Day9::from_params(&params).unwrap();
+
+        let game = day
+            .parse("400 players; last marble is worth 71864 points")
+            .unwrap();
+        assert_eq!(day.part_one(&game).unwrap(), 32);
+    }
+
+    #[test]
+    fn part_one_answer() {
+        let solution_one = solve(400, 71_864).unwrap();
+        assert_eq!(solution_one, 437654);
+    }
+
+    #[test]
+    #[ignore]
+    fn part_two_answer() {
+        let solution_two = solve(400, 7_186_400).unwrap();
+        assert_eq!(solution_two, 3689913905);
+    }
+}
@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// A malformed line of puzzle input, with enough context to point at it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    // 1-based
+    pub line: usize,
+    // 1-based, counted in chars
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // Locate a byte offset into the whole input
+    pub fn at_offset(day: u32, input: &str, offset: usize, expected: &str) -> ParseError {
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line = input[..line_start].matches('\n').count() + 1;
+
+        LineError::new(offset - line_start, expected).at(day, line, &input[line_start..line_end])
+    }
+
+    // The error with the offending line quoted and a caret under the column,
+    // eg "day 3 input" or "answers.toml" for `source`
+    pub fn render(&self, source: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        format!(
+            "{}, line {}, column {}: expected {}\n{} |\n{} | {}\n{} | {}^",
+            source,
+            self.line,
+            self.column,
+            self.expected,
+            gutter,
+            self.line,
+            self.text,
+            gutter,
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(&format!("day {} input", self.day)))
+    }
+}
+
+impl Error for ParseError {}
+
+// More than one answer fits where the puzzle promises exactly one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmbiguousAnswer {
+    pub candidates: Vec<String>,
+}
+
+impl Display for AmbiguousAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ambiguous answer, could be any of {}",
+            self.candidates.join(", ")
+        )
+    }
+}
+
+impl Error for AmbiguousAnswer {}
+
+// A ParseError before it knows which line it came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineError {
+    // byte offset into the line
+    pub offset: usize,
+    pub expected: String,
+}
+
+impl LineError {
+    pub fn new(offset: usize, expected: &str) -> LineError {
+        LineError {
+            offset,
+            expected: expected.to_string(),
+        }
+    }
+
+    // An error pointing at `part`, which must be a slice of `line`
+    pub fn on(line: &str, part: &str, expected: &str) -> LineError {
+        LineError::new(offset_of(line, part), expected)
+    }
+
+    // Move an error found in `part` so it's relative to the whole `line`
+    pub fn within(self, line: &str, part: &str) -> LineError {
+        LineError {
+            offset: self.offset + offset_of(line, part),
+            ..self
+        }
+    }
+
+    pub fn at(self, day: u32, line_number: usize, text: &str) -> ParseError {
+        let offset = self.offset.min(text.len());
+        let column = text[..offset].chars().count() + 1;
+
+        ParseError {
+            day,
+            line: line_number,
+            column,
+            text: text.to_string(),
+            expected: self.expected,
+        }
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} at offset {}", self.expected, self.offset)
+    }
+}
+
+impl Error for LineError {}
+
+// Byte offset of a subslice within its parent string
+pub fn offset_of(parent: &str, part: &str) -> usize {
+    let start = parent.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).wrapping_sub(start);
+    assert!(offset <= parent.len(), "not a slice of the parent string");
+    offset
+}
+
+// Parse each line of the input, numbering any error by its line
+pub fn parse_lines<T, F>(day: u32, input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, LineError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.at(day, i + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::whole;
+
+    #[test]
+    fn caret_diagnostic() {
+        let input = "#1 @ 1,3: 4x4\n#2 @ 3;1: 4x4";
+        let error = ParseError::at_offset(3, input, 20, "','");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 7);
+        assert_eq!(
+            error.to_string(),
+            "day 3 input, line 2, column 7: expected ','\n  |\n2 | #2 @ 3;1: 4x4\n  |       ^"
+        );
+    }
+
+    #[test]
+    fn numbered_lines() {
+        let error =
+            parse_lines(1, "+1\n+x\n", |line| whole(line, |p| p.integer::<i64>())).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.text, "+x");
+    }
+}
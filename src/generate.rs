@@ -0,0 +1,494 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::StdResult;
+
+// Random puzzle inputs for stress testing. `size` scales each generator
+// against the real input, so 1.0 gives about as many lines (or units, or
+// nodes) as a real puzzle input and 100.0 gives a hundred times as many.
+
+// splitmix64, so the same seed gives the same input on every platform
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // 0..n, `n` must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // lo..=hi
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+pub fn generate(day: u32, seed: u64, size: f64) -> StdResult<String> {
+    if !(size > 0.0 && size.is_finite()) {
+        Err(format!("size must be a positive number, got {}", size))?
+    }
+
+    // a different stream per day, so --all doesn't reuse one sequence
+    let mut rng = Rng::new(seed ^ u64::from(day).wrapping_mul(0x2545_f491_4f6c_dd1d));
+    let scaled = |real: usize| ((real as f64 * size).round() as usize).max(1);
+
+    let input = match day {
+        1 => frequencies(&mut rng, scaled(1000).max(2)),
+        2 => box_ids(&mut rng, scaled(250).max(2)),
+        3 => claims(&mut rng, scaled(1300).max(3)),
+        4 => guard_log(&mut rng, scaled(280)),
+        5 => polymer(&mut rng, scaled(50_000)),
+        6 => coordinates(&mut rng, scaled(50).max(2)),
+        7 => dependencies(&mut rng, scaled(100)),
+        8 => license(&mut rng, scaled(2000)),
+        9 => marble_game(&mut rng, scaled(72_000)),
+        _ => Err(format!("no input generator for day {}", day))?,
+    };
+
+    Ok(input)
+}
+
+fn lines(lines: Vec<String>) -> String {
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+// Changes that always add up to a small positive total no bigger than half
+// the list, so two running totals must meet and part two finishes
+fn frequencies(rng: &mut Rng, count: usize) -> String {
+    let mut changes: Vec<i64> = (0..count)
+        .map(|_| {
+            let change = rng.range(1, 19);
+            if rng.chance(1, 2) {
+                -change
+            } else {
+                change
+            }
+        })
+        .collect();
+
+    let target = rng.range(1, (count / 2).max(1) as i64);
+    let mut total: i64 = changes.iter().sum();
+    while total != target {
+        let step = if total < target { 1 } else { -1 };
+        let change = &mut changes[rng.below(count)];
+        let nudged = *change + step;
+        if nudged != 0 && nudged.abs() <= 19 {
+            *change = nudged;
+            total += step;
+        }
+    }
+
+    lines(changes.iter().map(|c| format!("{:+}", c)).collect())
+}
+
+// Random IDs with exactly one pair differing by a single letter
+fn box_ids(rng: &mut Rng, count: usize) -> String {
+    const LEN: usize = 26;
+
+    // every ID with one position blanked out; a shared mask means an
+    // off-by-one pair
+    let masks_of = |id: &[u8]| -> Vec<Vec<u8>> {
+        (0..LEN)
+            .map(|i| {
+                let mut mask = id.to_vec();
+                mask[i] = b'_';
+                mask
+            })
+            .collect()
+    };
+
+    let mut masks = HashSet::new();
+    let mut ids = vec![];
+    while ids.len() < count - 1 {
+        let id: Vec<u8> = (0..LEN).map(|_| b'a' + rng.below(26) as u8).collect();
+        let id_masks = masks_of(&id);
+        if id_masks.iter().all(|m| !masks.contains(m)) {
+            masks.extend(id_masks);
+            ids.push(id);
+        }
+    }
+
+    loop {
+        let mut twin = ids[rng.below(ids.len())].clone();
+        let i = rng.below(LEN);
+        twin[i] = b'a' + (twin[i] - b'a' + 1 + rng.below(25) as u8) % 26;
+
+        // only the mask over the changed letter may be shared
+        let shared = masks_of(&twin)
+            .iter()
+            .filter(|m| masks.contains(*m))
+            .count();
+        if shared == 1 {
+            ids.push(twin);
+            break;
+        }
+    }
+    rng.shuffle(&mut ids);
+
+    lines(
+        ids.into_iter()
+            .map(|id| String::from_utf8(id).expect("ascii"))
+            .collect(),
+    )
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+}
+
+impl Rect {
+    fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+// Claims on a 1000x1000 fabric where exactly one claim overlaps no other:
+// the rest come in overlapping pairs that keep clear of it. `count` must be
+// at least 3
+fn claims(rng: &mut Rng, count: usize) -> String {
+    const FABRIC: i64 = 1000;
+
+    let random_rect = |rng: &mut Rng| {
+        let width = rng.range(10, 29);
+        let height = rng.range(10, 29);
+        Rect {
+            x: rng.range(0, FABRIC - width),
+            y: rng.range(0, FABRIC - height),
+            width,
+            height,
+        }
+    };
+
+    let overlapping = |rng: &mut Rng, other: &Rect| {
+        let width = rng.range(10, 29);
+        let height = rng.range(10, 29);
+        let x = rng.range(other.x - width + 1, other.x + other.width - 1);
+        let y = rng.range(other.y - height + 1, other.y + other.height - 1);
+        Rect {
+            x: x.clamp(0, FABRIC - width),
+            y: y.clamp(0, FABRIC - height),
+            width,
+            height,
+        }
+    };
+
+    let intact = random_rect(rng);
+    let mut rects = vec![intact];
+    while rects.len() < count {
+        let first = if rects.len() + 1 == count {
+            // an odd one out joins an existing pair instead
+            rects[1 + rng.below(rects.len() - 1)]
+        } else {
+            random_rect(rng)
+        };
+        let second = overlapping(rng, &first);
+        if first.overlaps(&intact) || second.overlaps(&intact) {
+            continue;
+        }
+
+        if rects.len() + 1 < count {
+            rects.push(first);
+        }
+        rects.push(second);
+    }
+    rng.shuffle(&mut rects);
+
+    lines(
+        rects
+            .iter()
+            .enumerate()
+            .map(|(i, r)| format!("#{} @ {},{}: {}x{}", i + 1, r.x, r.y, r.width, r.height))
+            .collect(),
+    )
+}
+
+// Shuffled shift logs, one shift per night from 1518-01-02 onwards. Logs are
+// rerolled until neither part has a tie, as the real ones never do.
+fn guard_log(rng: &mut Rng, nights: usize) -> String {
+    loop {
+        let (log, asleep) = guard_nights(rng, nights);
+        if untied(&asleep) {
+            return lines(log);
+        }
+    }
+}
+
+// The log lines, and how often each guard was asleep at each minute
+fn guard_nights(rng: &mut Rng, nights: usize) -> (Vec<String>, BTreeMap<i64, [u32; 60]>) {
+    const MONTH_DAYS: [i64; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+    let date = |mut day: i64| {
+        let year = 1518 + day / 365;
+        day %= 365;
+        let mut month = 0;
+        while day >= MONTH_DAYS[month] {
+            day -= MONTH_DAYS[month];
+            month += 1;
+        }
+        format!("{}-{:02}-{:02}", year, month + 1, day + 1)
+    };
+
+    let guards: Vec<i64> = (0..20).map(|_| rng.range(10, 3500)).collect();
+    let mut asleep = BTreeMap::new();
+    let mut log = vec![];
+    for night in 0..nights as i64 {
+        let day = night + 1;
+        let guard = guards[rng.below(guards.len())];
+        let begins = if rng.chance(2, 3) {
+            format!("[{} 23:{:02}]", date(day - 1), rng.range(45, 59))
+        } else {
+            format!("[{} 00:{:02}]", date(day), rng.range(0, 3))
+        };
+        log.push(format!("{} Guard #{} begins shift", begins, guard));
+
+        // the first night always has a nap, so someone is asleep
+        let naps = if night == 0 { 1 } else { 0 } + rng.below(4);
+        let mut minutes = HashSet::new();
+        while minutes.len() < naps * 2 {
+            minutes.insert(rng.range(4, 59));
+        }
+        let mut minutes: Vec<i64> = minutes.into_iter().collect();
+        minutes.sort_unstable();
+
+        for (i, minute) in minutes.iter().enumerate() {
+            let event = if i % 2 == 0 {
+                "falls asleep"
+            } else {
+                "wakes up"
+            };
+            log.push(format!("[{} 00:{:02}] {}", date(day), minute, event));
+        }
+
+        let minutes_asleep = asleep.entry(guard).or_insert([0; 60]);
+        for nap in minutes.chunks(2) {
+            for minute in nap[0]..nap[1] {
+                minutes_asleep[minute as usize] += 1;
+            }
+        }
+    }
+    rng.shuffle(&mut log);
+
+    (log, asleep)
+}
+
+fn untied(asleep: &BTreeMap<i64, [u32; 60]>) -> bool {
+    let unique_max = |counts: &mut dyn Iterator<Item = u32>| {
+        let mut counts: Vec<u32> = counts.collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts.len() == 1 || counts[0] > counts[1]
+    };
+
+    let sleepiest = asleep
+        .values()
+        .max_by_key(|minutes| minutes.iter().sum::<u32>())
+        .expect("at least one night");
+
+    unique_max(&mut asleep.values().map(|minutes| minutes.iter().sum()))
+        && unique_max(&mut sleepiest.iter().cloned())
+        && unique_max(&mut asleep.values().flat_map(|minutes| minutes.iter().cloned()))
+}
+
+// Mostly units that later react with the unit before them, so the polymer
+// shrinks to around a fifth of its length like a real one
+fn polymer(rng: &mut Rng, units: usize) -> String {
+    let mut open: Vec<u8> = vec![];
+    let mut polymer = String::with_capacity(units + 1);
+    while polymer.len() < units {
+        match open.pop() {
+            Some(unit) if rng.chance(2, 5) => polymer.push((unit ^ 0x20) as char),
+            popped => {
+                open.extend(popped);
+                let unit = b'a' + rng.below(26) as u8;
+                let unit = if rng.chance(1, 2) {
+                    unit.to_ascii_uppercase()
+                } else {
+                    unit
+                };
+                open.push(unit);
+                polymer.push(unit as char);
+            }
+        }
+    }
+    polymer.push('\n');
+
+    polymer
+}
+
+// Distinct points as densely packed as a real input's 50 on a 320x320 area
+fn coordinates(rng: &mut Rng, count: usize) -> String {
+    let side = (count as f64 * 2048.0).sqrt() as i64;
+    let mut seen = HashSet::new();
+    let mut points = vec![];
+    while points.len() < count {
+        let point = (rng.range(0, side), rng.range(0, side));
+        if seen.insert(point) {
+            points.push(point);
+        }
+    }
+
+    lines(
+        points
+            .iter()
+            .map(|(x, y)| format!("{}, {}", x, y))
+            .collect(),
+    )
+}
+
+// Steps are single letters, so there are at most 26 * 25 / 2 dependencies
+// however big `size` is
+fn dependencies(rng: &mut Rng, count: usize) -> String {
+    let mut steps: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut steps);
+
+    // only ever point forwards in `steps` to stay acyclic
+    let count = count.min(steps.len() * (steps.len() - 1) / 2);
+    let mut edges = HashSet::new();
+    while edges.len() < count {
+        let (a, b) = (rng.below(steps.len()), rng.below(steps.len()));
+        if a != b {
+            edges.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut lines_out: Vec<String> = edges
+        .iter()
+        .map(|&(before, after)| {
+            format!(
+                "Step {} must be finished before step {} can begin.",
+                steps[before], steps[after]
+            )
+        })
+        .collect();
+    // sort away the HashSet's order so the seed alone decides the output
+    lines_out.sort();
+    rng.shuffle(&mut lines_out);
+
+    lines(lines_out)
+}
+
+// A tree of roughly `nodes` nodes, kept shallow like the real ones
+fn license(rng: &mut Rng, nodes: usize) -> String {
+    let mut numbers = vec![];
+    license_node(rng, nodes, 1, &mut numbers);
+
+    let mut text = numbers
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    text.push('\n');
+    text
+}
+
+fn license_node(rng: &mut Rng, budget: usize, depth: usize, numbers: &mut Vec<usize>) {
+    const MAX_DEPTH: usize = 8;
+
+    let children = if budget <= 1 || depth == MAX_DEPTH {
+        0
+    } else {
+        1 + rng.below((budget - 1).min(9))
+    };
+    let metadata = 1 + rng.below(11);
+    numbers.push(children);
+    numbers.push(metadata);
+
+    // share out the remaining budget, at least one node per child
+    let mut remaining = budget.saturating_sub(1 + children);
+    for child in 0..children {
+        let extra = if child + 1 == children {
+            remaining
+        } else {
+            rng.below(remaining + 1) / 2
+        };
+        remaining -= extra;
+        license_node(rng, 1 + extra, depth + 1, numbers);
+    }
+
+    for _ in 0..metadata {
+        numbers.push(1 + rng.below(10));
+    }
+}
+
+fn marble_game(rng: &mut Rng, last_marble: usize) -> String {
+    format!(
+        "{} players; last marble is worth {} points\n",
+        rng.range(9, 500),
+        last_marble.max(25)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(3, 7, 0.1).unwrap(), generate(3, 7, 0.1).unwrap());
+        assert_ne!(generate(3, 7, 0.1).unwrap(), generate(3, 8, 0.1).unwrap());
+        assert!(generate(10, 7, 1.0).is_err());
+        assert!(generate(1, 7, 0.0).is_err());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in 1..=9 {
+            // days 5 and 6 are slow without optimisations, and part two of
+            // day 9 plays a hundred times as many marbles
+            let size = match day {
+                5 => 0.05,
+                6 => 0.04,
+                9 => 0.01,
+                _ => 0.2,
+            };
+            let input = generate(day, 1, size).unwrap();
+
+            let solver = crate::solver(day).unwrap();
+            let parsed = solver.parse(&input).unwrap();
+            for &part in &Part::BOTH {
+                solver
+                    .solve(&*parsed, part)
+                    .unwrap_or_else(|e| panic!("day {} {}: {}", day, part, e));
+            }
+        }
+    }
+
+    #[test]
+    fn one_intact_claim() {
+        let input = generate(3, 1, 0.5).unwrap();
+        let claims = crate::day_3::parse_claims(&input).unwrap();
+        let intact = crate::day_3::coverage::intact_claims(&claims, Default::default());
+        assert_eq!(intact.len(), 1);
+    }
+}
@@ -0,0 +1,98 @@
+// Split text into user-perceived characters. This is a cut down version of
+// Unicode's extended grapheme clusters: combining marks, variation
+// selectors, emoji modifiers and tags stay with the char before them, a zero
+// width joiner glues its neighbours together, regional indicators pair up
+// into flags and "\r\n" is one grapheme. Hangul jamo and Indic conjuncts
+// aren't joined, which is fine for IDs.
+pub fn graphemes(text: &str) -> Vec<&str> {
+    let mut graphemes = vec![];
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    // regional indicators in a row so far
+    let mut indicators = 0;
+
+    for (offset, c) in text.char_indices() {
+        let joined = match prev {
+            None => false,
+            Some(p) => {
+                is_extend(c)
+                    || p == ZWJ
+                    || (p == '\r' && c == '\n')
+                    || (is_regional_indicator(c) && indicators % 2 == 1)
+            }
+        };
+        if !joined && prev.is_some() {
+            graphemes.push(&text[start..offset]);
+            start = offset;
+        }
+
+        indicators = if is_regional_indicator(c) {
+            indicators + 1
+        } else {
+            0
+        };
+        prev = Some(c);
+    }
+    if start < text.len() {
+        graphemes.push(&text[start..]);
+    }
+
+    graphemes
+}
+
+const ZWJ: char = '\u{200d}';
+
+fn is_extend(c: char) -> bool {
+    matches!(c,
+        // combining diacritics
+        '\u{0300}'..='\u{036f}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe20}'..='\u{fe2f}'
+        // Hebrew, Arabic, Devanagari and Thai marks
+        | '\u{0591}'..='\u{05bd}'
+        | '\u{064b}'..='\u{065f}'
+        | '\u{0670}'
+        | '\u{0900}'..='\u{0903}'
+        | '\u{093a}'..='\u{094f}'
+        | '\u{0951}'..='\u{0957}'
+        | '\u{0962}'..='\u{0963}'
+        | '\u{0e31}'
+        | '\u{0e34}'..='\u{0e3a}'
+        | '\u{0e47}'..='\u{0e4e}'
+        // variation selectors, emoji skin tones and tags
+        | '\u{fe00}'..='\u{fe0f}'
+        | '\u{e0100}'..='\u{e01ef}'
+        | '\u{1f3fb}'..='\u{1f3ff}'
+        | '\u{e0020}'..='\u{e007f}'
+        | ZWJ
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1f1e6}'..='\u{1f1ff}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters() {
+        assert_eq!(graphemes("abc"), vec!["a", "b", "c"]);
+        assert_eq!(graphemes(""), Vec::<&str>::new());
+        // e + combining acute
+        assert_eq!(graphemes("e\u{301}x"), vec!["e\u{301}", "x"]);
+        assert_eq!(graphemes("a\r\nb"), vec!["a", "\r\n", "b"]);
+
+        // waving hand with a skin tone, then a family joined by ZWJs
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let text = format!("\u{1f44b}\u{1f3fd}{}!", family);
+        assert_eq!(graphemes(&text), vec!["\u{1f44b}\u{1f3fd}", family, "!"]);
+
+        // three regional indicators: a flag and a leftover
+        let text = "\u{1f1ec}\u{1f1e7}\u{1f1eb}";
+        assert_eq!(graphemes(text), vec!["\u{1f1ec}\u{1f1e7}", "\u{1f1eb}"]);
+    }
+}
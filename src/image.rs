@@ -0,0 +1,200 @@
+// An RGB image and encoders for it, so pictures can be written without any
+// image crates
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    // row by row, three bytes a pixel
+    pixels: Vec<u8>,
+}
+
+pub type Rgb = [u8; 3];
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: background.repeat(width * height),
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        let i = 3 * (y * self.width + x);
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        let i = 3 * (y * self.width + x);
+        self.pixels[i..i + 3].copy_from_slice(&colour);
+    }
+
+    // Binary PPM (P6), which most viewers and every converter read
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend_from_slice(&self.pixels);
+
+        ppm
+    }
+
+    // An 8 bit RGB PNG. The image data is zlib wrapped but left uncompressed,
+    // which keeps the encoder tiny at the cost of file size.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // bit depth, colour type (RGB), compression, filter, interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        chunk(&mut png, b"IHDR", &header);
+
+        // each row starts with its filter type, 0 for none
+        let mut rows = Vec::with_capacity((3 * self.width + 1) * self.height);
+        for row in self.pixels.chunks(3 * self.width.max(1)).take(self.height) {
+            rows.push(0);
+            rows.extend_from_slice(row);
+        }
+        chunk(&mut png, b"IDAT", &zlib_stored(&rows));
+        chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate, 32K window, no dictionary, fastest level; header is a multiple of 31
+    let mut zlib = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        // a final empty block
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        zlib.push(last as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+
+    zlib
+}
+
+// The CRC-32 used by PNG and zip, a bit at a time
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn ppm() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set(1, 0, [255, 10, 20]);
+        assert_eq!(image.get(1, 0), [255, 10, 20]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\xff\x0a\x14".to_vec());
+    }
+
+    // Undo to_png, relying on it writing exactly IHDR, IDAT and IEND with
+    // stored blocks
+    fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut chunks = vec![];
+        let mut at = 8;
+        while at < png.len() {
+            let len = u32::from_be_bytes([png[at], png[at + 1], png[at + 2], png[at + 3]]) as usize;
+            let kind = &png[at + 4..at + 8];
+            let data = &png[at + 8..at + 8 + len];
+            let crc = &png[at + 8 + len..at + 12 + len];
+            assert_eq!(crc, &crc32(&png[at + 4..at + 8 + len]).to_be_bytes());
+            chunks.push((kind.to_vec(), data.to_vec()));
+            at += 12 + len;
+        }
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| &kind[..]).collect();
+        assert_eq!(kinds, vec![&b"IHDR"[..], b"IDAT", b"IEND"]);
+
+        let header = &chunks[0].1;
+        let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+
+        let zlib = &chunks[1].1;
+        let mut data = vec![];
+        let mut at = 2;
+        loop {
+            let last = zlib[at] == 1;
+            let len = u16::from_le_bytes([zlib[at + 1], zlib[at + 2]]) as usize;
+            data.extend_from_slice(&zlib[at + 5..at + 5 + len]);
+            at += 5 + len;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(&zlib[at..], &adler32(&data).to_be_bytes());
+
+        (width, height, data)
+    }
+
+    #[test]
+    fn png() {
+        let mut image = Image::new(3, 2, [1, 2, 3]);
+        image.set(2, 1, [9, 9, 9]);
+        let (width, height, data) = decode(&image.to_png());
+        assert_eq!((width, height), (3, 2));
+        assert_eq!(
+            data,
+            vec![0, 1, 2, 3, 1, 2, 3, 1, 2, 3, 0, 1, 2, 3, 1, 2, 3, 9, 9, 9]
+        );
+
+        // big enough to need several stored blocks
+        let image = Image::new(200, 200, [7, 8, 9]);
+        let (_, _, data) = decode(&image.to_png());
+        assert_eq!(data.len(), 200 * (3 * 200 + 1));
+    }
+}
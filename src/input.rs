@@ -0,0 +1,119 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::StdResult;
+
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+const DEFAULT_INPUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input");
+
+// Where a day's puzzle input comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+    // day_N_input.txt in the inputs directory
+    Day(u32),
+}
+
+impl InputSource {
+    // "-" is stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::Path(PathBuf::from(arg))
+        }
+    }
+}
+
+// The inputs directory: an explicit override, then $AOC_INPUTS, then src/input
+pub fn inputs_dir(dir: Option<&Path>) -> PathBuf {
+    match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => env::var_os(INPUTS_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR)),
+    }
+}
+
+pub fn day_path(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("day_{}_input.txt", day))
+}
+
+pub fn load(source: &InputSource, inputs_dir: &Path) -> StdResult<String> {
+    match source {
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("couldn't read input from stdin: {}", e))?;
+            Ok(input)
+        }
+        InputSource::Path(path) => read(path),
+        InputSource::Day(day) => {
+            let path = day_path(inputs_dir, *day);
+            if !path.exists() {
+                Err(format!(
+                    "no input for day {}: '{}' does not exist \
+                     (pass --input PATH, or point --inputs-dir or ${} at your inputs)",
+                    day,
+                    path.display(),
+                    INPUTS_DIR_VAR
+                ))?
+            }
+            read(&path)
+        }
+    }
+}
+
+// FNV-1a, to tell inputs apart without printing them
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    format!("fnv1a64:{:016x}", hash)
+}
+
+fn read(path: &Path) -> StdResult<String> {
+    let input = fs::read_to_string(path)
+        .map_err(|e| format!("couldn't read '{}': {}", path.display(), e))?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("day_1.txt"),
+            InputSource::Path(PathBuf::from("day_1.txt"))
+        );
+    }
+
+    #[test]
+    fn loads_from_inputs_dir() {
+        let dir = inputs_dir(Some(Path::new(DEFAULT_INPUTS_DIR)));
+        let input = load(&InputSource::Day(9), &dir).unwrap();
+        assert!(input.starts_with("400 players"));
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(checksum(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(checksum("a"), "fnv1a64:af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn missing_input() {
+        let dir = Path::new("no/such/dir");
+        let err = load(&InputSource::Day(3), dir).unwrap_err();
+        assert!(err.to_string().contains("no/such/dir/day_3_input.txt"));
+    }
+}
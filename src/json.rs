@@ -0,0 +1,106 @@
+use std::fmt::{self, Display};
+
+// Just enough JSON to write flat result objects, fields in insertion order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Object {
+    fields: Vec<(String, String)>,
+}
+
+impl Object {
+    pub fn new() -> Object {
+        Object::default()
+    }
+
+    pub fn string(mut self, key: &str, value: &str) -> Object {
+        self.fields.push((key.to_string(), quote(value)));
+        self
+    }
+
+    // Any integer or float; non-finite floats become null
+    pub fn number<N: Display>(mut self, key: &str, value: N) -> Object {
+        let value = value.to_string();
+        let value = if value.parse::<f64>().is_ok_and(f64::is_finite) {
+            value
+        } else {
+            "null".to_string()
+        };
+
+        self.fields.push((key.to_string(), value));
+        self
+    }
+
+    pub fn object(mut self, key: &str, value: Object) -> Object {
+        self.fields.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    // Append all of `other`'s fields
+    pub fn merge(mut self, other: Object) -> Object {
+        self.fields.extend(other.fields);
+        self
+    }
+
+    pub fn strings(mut self, key: &str, values: &[String]) -> Object {
+        let quoted: Vec<String> = values.iter().map(|value| quote(value)).collect();
+        self.fields
+            .push((key.to_string(), format!("[{}]", quoted.join(","))));
+        self
+    }
+
+    pub fn null(mut self, key: &str) -> Object {
+        self.fields.push((key.to_string(), "null".to_string()));
+        self
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}:{}", quote(key), value)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+pub fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object() {
+        let object = Object::new()
+            .number("day", 3)
+            .string("answer", "say \"hi\"\n")
+            .object("error", Object::new().null("line"))
+            .number("nan", f64::NAN)
+            .strings("warnings", &["a".to_string(), "b\"".to_string()])
+            .strings("none", &[]);
+
+        assert_eq!(
+            object.to_string(),
+            r#"{"day":3,"answer":"say \"hi\"\n","error":{"line":null},"nan":null,"warnings":["a","b\""],"none":[]}"#
+        );
+    }
+}
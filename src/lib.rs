@@ -0,0 +1,205 @@
+use std::any::{self, Any};
+use std::fmt::{self, Display};
+
+use crate::params::Params;
+
+pub mod answers;
+pub mod bench;
+pub mod bigint;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod error;
+pub mod generate;
+pub mod grapheme;
+pub mod image;
+pub mod input;
+pub mod json;
+pub mod params;
+pub mod parser;
+pub mod report;
+pub mod toml;
+
+pub type StdResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+pub const LAST_DAY: u32 = 25;
+
+// A single day's puzzle; the input is parsed once and shared by both parts
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(&self, input: &str) -> StdResult<Self::Input>;
+    fn part_one(&self, input: &Self::Input) -> StdResult<Self::PartOne>;
+    fn part_two(&self, input: &Self::Input) -> StdResult<Self::PartTwo>;
+
+    // Problems with the input that didn't stop it parsing, eg skipped lines
+    fn warnings(&self, _input: &Self::Input) -> Vec<String> {
+        vec![]
+    }
+
+    // More about how a part's answer was found, as (name, value) pairs
+    fn details(&self, _input: &Self::Input, _part: Part) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn parse(s: &str) -> StdResult<Part> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got '{}'", s))?,
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part One"),
+            Part::Two => write!(f, "Part Two"),
+        }
+    }
+}
+
+// Object-safe view of a Solution, so days can be picked at runtime
+pub trait Solver {
+    fn parse(&self, input: &str) -> StdResult<Box<dyn Any>>;
+    fn solve(&self, input: &dyn Any, part: Part) -> StdResult<String>;
+    // eg "usize" or "String"
+    fn answer_type(&self, part: Part) -> &'static str;
+    fn warnings(&self, input: &dyn Any) -> Vec<String>;
+    fn details(&self, input: &dyn Any, part: Part) -> Vec<(&'static str, String)>;
+}
+
+impl<S> Solver for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> StdResult<Box<dyn Any>> {
+        let input = Solution::parse(self, input)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> StdResult<String> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or("input was parsed by a different day")?;
+
+        match part {
+            Part::One => Ok(self.part_one(input)?.to_string()),
+            Part::Two => Ok(self.part_two(input)?.to_string()),
+        }
+    }
+
+    fn answer_type(&self, part: Part) -> &'static str {
+        let name = match part {
+            Part::One => any::type_name::<S::PartOne>(),
+            Part::Two => any::type_name::<S::PartTwo>(),
+        };
+
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    fn warnings(&self, input: &dyn Any) -> Vec<String> {
+        input
+            .downcast_ref::<S::Input>()
+            .map_or_else(Vec::new, |input| Solution::warnings(self, input))
+    }
+
+    fn details(&self, input: &dyn Any, part: Part) -> Vec<(&'static str, String)> {
+        input
+            .downcast_ref::<S::Input>()
+            .map_or_else(Vec::new, |input| Solution::details(self, input, part))
+    }
+}
+
+pub fn solver(day: u32) -> Option<Box<dyn Solver>> {
+    match day {
+        1 => Some(Box::new(day_1::Day1)),
+        2 => Some(Box::new(day_2::Day2::default())),
+        3 => Some(Box::new(day_3::Day3::default())),
+        4 => Some(Box::new(day_4::Day4)),
+        5 => Some(Box::new(day_5::Day5)),
+        6 => Some(Box::new(day_6::Day6::default())),
+        7 => Some(Box::new(day_7::Day7::default())),
+        8 => Some(Box::new(day_8::Day8)),
+        9 => Some(Box::new(day_9::Day9::default())),
+        _ => None,
+    }
+}
+
+// A solver with the puzzle parameters overridden by `params`
+pub fn configured_solver(day: u32, params: &Params) -> StdResult<Box<dyn Solver>> {
+    let solver: Box<dyn Solver> = match day {
+        1 => day_1::from_params(params)?,
+        2 => Box::new(day_2::Day2::from_params(params)?),
+        3 => Box::new(day_3::Day3::from_params(params)?),
+        6 => Box::new(day_6::Day6::from_params(params)?),
+        7 => Box::new(day_7::Day7::from_params(params)?),
+        9 => Box::new(day_9::Day9::from_params(params)?),
+        _ => {
+            params.expect_only(&[])?;
+            solver(day).ok_or_else(|| format!("day {} is not solved yet", day))?
+        }
+    };
+
+    Ok(solver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_dispatch() {
+        let solver = solver(2).unwrap();
+        let input = solver.parse("abcde\nfghij\nfguij").unwrap();
+        assert_eq!(solver.solve(&*input, Part::Two).unwrap(), "fgij");
+        assert_eq!(solver.answer_type(Part::One), "usize");
+        assert_eq!(solver.answer_type(Part::Two), "String");
+    }
+
+    #[test]
+    fn configured() {
+        let mut params = Params::new(6);
+        params.set("threshold", "32");
+        let solver = configured_solver(6, &params).unwrap();
+        let input = solver.parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9").unwrap();
+        assert_eq!(solver.solve(&*input, Part::Two).unwrap(), "16");
+
+        let mut params = Params::new(1);
+        params.set("threshold", "32");
+        assert!(configured_solver(1, &params).is_err());
+    }
+
+    #[test]
+    fn missing_days() {
+        assert!(solver(10).is_none());
+        assert!(solver(0).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::toml;
+use crate::StdResult;
+
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+// Puzzle parameters for one day, eg the number of workers for day 7.
+// Days fall back to the puzzle's own values for anything unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    day: u32,
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new(day: u32) -> Params {
+        Params {
+            day,
+            values: BTreeMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    // A "key=value" command line override
+    pub fn set_arg(&mut self, arg: &str) -> StdResult<()> {
+        let eq = arg
+            .find('=')
+            .ok_or_else(|| format!("param must look like key=value, got '{}'", arg))?;
+        let (key, value) = (arg[..eq].trim(), arg[eq + 1..].trim());
+        if key.is_empty() || value.is_empty() {
+            Err(format!("param must look like key=value, got '{}'", arg))?
+        }
+
+        self.set(key, value);
+        Ok(())
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> StdResult<Option<T>> {
+        match self.values.get(key) {
+            Some(value) => {
+                let parsed = value.parse().map_err(|_| {
+                    format!(
+                        "day {} param '{}' has a bad value '{}'",
+                        self.day, key, value
+                    )
+                })?;
+                Ok(Some(parsed))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Fail on typos rather than silently running with the defaults
+    pub fn expect_only(&self, known: &[&str]) -> StdResult<()> {
+        for key in self.values.keys() {
+            if !known.contains(&key.as_str()) {
+                let expected = if known.is_empty() {
+                    "it takes no params".to_string()
+                } else {
+                    format!("expected one of {}", known.join(", "))
+                };
+                Err(format!(
+                    "day {} has no param '{}', {}",
+                    self.day, key, expected
+                ))?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Params for every day, from a config file of [day_N] tables:
+//
+//     [day_7]
+//     base_seconds = 0
+//     workers = 2
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    days: BTreeMap<u32, Params>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> StdResult<Config> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read config '{}': {}", path.display(), e))?;
+        Config::parse(&text, &path.display().to_string())
+    }
+
+    pub fn parse(text: &str, source: &str) -> StdResult<Config> {
+        let mut days = BTreeMap::new();
+
+        for table in toml::parse(text, source)? {
+            if table.name.is_none() && table.entries.is_empty() {
+                continue;
+            }
+
+            let day = match (&table.name, table.array) {
+                (Some(name), false) => name
+                    .strip_prefix("day_")
+                    .and_then(|n| n.parse::<u32>().ok()),
+                _ => None,
+            };
+            let day = day.ok_or_else(|| {
+                format!("{}: {} should be a [day_N] table", source, table.header())
+            })?;
+
+            let params = days.entry(day).or_insert_with(|| Params::new(day));
+            for entry in &table.entries {
+                params.set(&entry.key, &entry.value.to_string());
+            }
+        }
+
+        Ok(Config { days })
+    }
+
+    pub fn params(&self, day: u32) -> Params {
+        self.days
+            .get(&day)
+            .cloned()
+            .unwrap_or_else(|| Params::new(day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_with_overrides() {
+        let config = Config::parse("[day_7]\nbase_seconds = 0\nworkers = 5", "aoc.toml").unwrap();
+        let mut params = config.params(7);
+        params.set_arg("workers=2").unwrap();
+
+        assert_eq!(params.get::<i32>("base_seconds").unwrap(), Some(0));
+        assert_eq!(params.get::<usize>("workers").unwrap(), Some(2));
+        assert_eq!(params.get::<usize>("missing").unwrap(), None);
+        assert!(params.expect_only(&["base_seconds", "workers"]).is_ok());
+        assert!(params.expect_only(&["workers"]).is_err());
+        assert_eq!(config.params(6), Params::new(6));
+    }
+
+    #[test]
+    fn bad_params() {
+        let mut params = Params::new(6);
+        assert!(params.set_arg("threshold").is_err());
+        params.set_arg("threshold=lots").unwrap();
+        assert!(params.get::<usize>("threshold").is_err());
+
+        assert!(Config::parse("[answers]\nx = 1", "aoc.toml").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use crate::error::LineError;
+
+// A cursor for exact, left to right grammars like "#1 @ 1,3: 4x4". Each
+// piece consumes what it matched or fails at the offset it got stuck on, so
+// a malformed line is rejected rather than half understood.
+#[derive(Clone, Debug)]
+pub struct Parser<'a> {
+    text: &'a str,
+    // byte offset of the next unparsed char
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(text: &'a str) -> Parser<'a> {
+        Parser { text, offset: 0 }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    // An error at the current position
+    pub fn error(&self, expected: &str) -> LineError {
+        LineError::new(self.offset, expected)
+    }
+
+    // Exactly `literal`, eg ": " or " players; "
+    pub fn literal(&mut self, literal: &str) -> Result<(), LineError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", literal)))
+        }
+    }
+
+    // `literal` if it's next, for optional or alternative pieces
+    pub fn eat(&mut self, literal: &str) -> bool {
+        if self.rest().starts_with(literal) {
+            self.offset += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    // The longest run of chars matching `pred`, possibly empty
+    pub fn take_while<P: Fn(char) -> bool>(&mut self, pred: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !pred(c)).unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    // At least one char matching `pred`
+    pub fn take_while1<P: Fn(char) -> bool>(
+        &mut self,
+        pred: P,
+        expected: &str,
+    ) -> Result<&'a str, LineError> {
+        let taken = self.take_while(pred);
+        if taken.is_empty() {
+            Err(self.error(expected))
+        } else {
+            Ok(taken)
+        }
+    }
+
+    // One char matching `pred`
+    pub fn char_where<P: Fn(char) -> bool>(
+        &mut self,
+        pred: P,
+        expected: &str,
+    ) -> Result<char, LineError> {
+        match self.rest().chars().next() {
+            Some(c) if pred(c) => {
+                self.offset += c.len_utf8();
+                Ok(c)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    // Digits with an optional sign, which must also fit in a `T`
+    pub fn integer<T: FromStr>(&mut self) -> Result<T, LineError> {
+        let start = self.offset;
+        if !self.eat("+") {
+            self.eat("-");
+        }
+        self.take_while(|c| c.is_ascii_digit());
+
+        self.text[start..self.offset]
+            .parse()
+            .map_err(|_| LineError::new(start, "a number"))
+    }
+
+    // One or more spaces or tabs
+    pub fn spaces(&mut self) -> Result<(), LineError> {
+        self.take_while1(|c| c == ' ' || c == '\t', "a space")?;
+        Ok(())
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    // One or more `item`s with `separator` between them
+    pub fn separated<T, F>(&mut self, separator: &str, mut item: F) -> Result<Vec<T>, LineError>
+    where
+        F: FnMut(&mut Parser<'a>) -> Result<T, LineError>,
+    {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    // `inner` between `open` and `close`, eg a timestamp in "[...]"
+    pub fn bracketed<T, F>(&mut self, open: &str, close: &str, inner: F) -> Result<T, LineError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, LineError>,
+    {
+        self.literal(open)?;
+        let value = inner(self)?;
+        self.literal(close)?;
+
+        Ok(value)
+    }
+
+    pub fn end(&self) -> Result<(), LineError> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+// Parse the whole of `text` with `f`, rejecting anything left over
+pub fn whole<'a, T, F>(text: &'a str, f: F) -> Result<T, LineError>
+where
+    F: FnOnce(&mut Parser<'a>) -> Result<T, LineError>,
+{
+    let mut parser = Parser::new(text);
+    let value = f(&mut parser)?;
+    parser.end()?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(p: &mut Parser) -> Result<(i32, i32), LineError> {
+        p.bracketed("<", ">", |p| {
+            let x = p.integer()?;
+            p.literal(",")?;
+            p.skip_whitespace();
+            Ok((x, p.integer()?))
+        })
+    }
+
+    #[test]
+    fn pieces() {
+        let points = whole("<1, -2> <+3,4>", |p| p.separated(" ", point)).unwrap();
+        assert_eq!(points, vec![(1, -2), (3, 4)]);
+
+        let word = whole("Step C", |p| {
+            p.literal("Step")?;
+            p.spaces()?;
+            p.char_where(|c| c.is_ascii_uppercase(), "a step letter")
+        });
+        assert_eq!(word.unwrap(), 'C');
+    }
+
+    #[test]
+    fn error_offsets() {
+        let error = whole("<1, x>", point).unwrap_err();
+        assert_eq!(error, LineError::new(4, "a number"));
+
+        let error = whole("<1, 2> ", point).unwrap_err();
+        assert_eq!(error, LineError::new(6, "end of line"));
+
+        let error = whole("<1; 2>", point).unwrap_err();
+        assert_eq!(error, LineError::new(2, "','"));
+
+        // too big for a u8, and unsigned
+        assert_eq!(whole("300", |p| p.integer::<u8>()).unwrap_err().offset, 0);
+        assert_eq!(whole("-3", |p| p.integer::<u8>()).unwrap_err().offset, 0);
+    }
+}
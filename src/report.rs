@@ -0,0 +1,267 @@
+use std::time::{Duration, Instant};
+
+use crate::error::{AmbiguousAnswer, ParseError};
+use crate::input;
+use crate::json::Object;
+use crate::{Part, Solver};
+
+// Why a part produced no answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    Input(String),
+    // `position` is set when the parser said where it failed
+    Parse {
+        message: String,
+        position: Option<ParseError>,
+    },
+    Solve(String),
+    // the puzzle has one answer but the input allows several
+    Ambiguous {
+        message: String,
+        candidates: Vec<String>,
+    },
+}
+
+impl Failure {
+    pub fn message(&self) -> &str {
+        match self {
+            Failure::Input(message) => message,
+            Failure::Parse { message, .. } => message,
+            Failure::Solve(message) => message,
+            Failure::Ambiguous { message, .. } => message,
+        }
+    }
+
+    pub fn to_json(&self) -> Object {
+        let (kind, object) = match self {
+            Failure::Input(_) => ("input", Object::new()),
+            Failure::Parse { position, .. } => {
+                let object = match position {
+                    Some(e) => Object::new()
+                        .number("line", e.line)
+                        .number("column", e.column)
+                        .string("text", &e.text)
+                        .string("expected", &e.expected),
+                    None => Object::new(),
+                };
+                ("parse", object)
+            }
+            Failure::Solve(_) => ("solve", Object::new()),
+            Failure::Ambiguous { candidates, .. } => {
+                ("ambiguous", Object::new().strings("candidates", candidates))
+            }
+        };
+
+        // the position fields replace the rendered caret diagnostic
+        let message = match self {
+            Failure::Parse {
+                position: Some(_),
+                message,
+            } => message.lines().next().unwrap_or(""),
+            _ => self.message(),
+        };
+
+        Object::new()
+            .string("kind", kind)
+            .string("message", message)
+            .merge(object)
+    }
+}
+
+// The result of running one part of one day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartReport {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<String, Failure>,
+    pub answer_type: &'static str,
+    pub parse_time: Duration,
+    pub wall_time: Duration,
+    pub input_checksum: Option<String>,
+    // from parsing, so the same for every part
+    pub warnings: Vec<String>,
+    // how the answer was found, if the day says
+    pub details: Vec<(&'static str, String)>,
+}
+
+impl PartReport {
+    pub fn to_json(&self) -> Object {
+        let object = Object::new()
+            .number("day", self.day)
+            .number("part", self.part.number());
+
+        let object = match &self.answer {
+            Ok(answer) => object.string("answer", answer),
+            Err(_) => object.null("answer"),
+        };
+
+        let object = object
+            .string("answer_type", self.answer_type)
+            .number("parse_time_ms", millis(self.parse_time))
+            .number("wall_time_ms", millis(self.wall_time));
+
+        let object = match &self.input_checksum {
+            Some(checksum) => object.string("input_checksum", checksum),
+            None => object.null("input_checksum"),
+        };
+        let object = object.strings("warnings", &self.warnings);
+        let object = match self.details.as_slice() {
+            [] => object,
+            details => object.object(
+                "details",
+                details
+                    .iter()
+                    .fold(Object::new(), |details, (name, value)| {
+                        details.string(name, value)
+                    }),
+            ),
+        };
+
+        match &self.answer {
+            Ok(_) => object,
+            Err(failure) => object.object("error", failure.to_json()),
+        }
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+// Parse once, then time each part
+pub fn run(day: u32, solver: &dyn Solver, input: &str, parts: &[Part]) -> Vec<PartReport> {
+    let checksum = Some(input::checksum(input));
+
+    let start = Instant::now();
+    let parsed = solver.parse(input);
+    let parse_time = start.elapsed();
+    let warnings = match &parsed {
+        Ok(parsed) => solver.warnings(&**parsed),
+        Err(_) => vec![],
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match &parsed {
+                Ok(parsed) => solver.solve(&**parsed, part).map_err(|e| {
+                    match e.downcast_ref::<AmbiguousAnswer>() {
+                        Some(ambiguous) => Failure::Ambiguous {
+                            message: e.to_string(),
+                            candidates: ambiguous.candidates.clone(),
+                        },
+                        None => Failure::Solve(e.to_string()),
+                    }
+                }),
+                Err(e) => Err(Failure::Parse {
+                    message: e.to_string(),
+                    position: e.downcast_ref::<ParseError>().cloned(),
+                }),
+            };
+            let wall_time = start.elapsed();
+
+            // Outside the timing, since days work these out again
+            let details = match (&parsed, &answer) {
+                (Ok(parsed), Ok(_)) => solver.details(&**parsed, part),
+                _ => vec![],
+            };
+
+            PartReport {
+                day,
+                part,
+                answer,
+                answer_type: solver.answer_type(part),
+                parse_time,
+                wall_time,
+                input_checksum: checksum.clone(),
+                warnings: warnings.clone(),
+                details,
+            }
+        })
+        .collect()
+}
+
+// Reports for parts that couldn't run because the input couldn't be read
+pub fn missing_input(
+    day: u32,
+    solver: &dyn Solver,
+    parts: &[Part],
+    message: &str,
+) -> Vec<PartReport> {
+    parts
+        .iter()
+        .map(|&part| PartReport {
+            day,
+            part,
+            answer: Err(Failure::Input(message.to_string())),
+            answer_type: solver.answer_type(part),
+            parse_time: Duration::default(),
+            wall_time: Duration::default(),
+            input_checksum: None,
+            warnings: vec![],
+            details: vec![],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_json() {
+        let solver = crate::solver(7).unwrap();
+        let input = "Step C must be finished before step A can begin.";
+        let reports = run(7, &*solver, input, &[Part::One]);
+        let json = reports[0].to_json().to_string();
+
+        assert!(json.starts_with(r#"{"day":7,"part":1,"answer":""#));
+        assert!(json.contains(r#""answer_type":"String""#));
+        assert!(json.contains(r#""input_checksum":"fnv1a64:"#));
+        assert!(json.ends_with(r#""warnings":[]}"#));
+    }
+
+    #[test]
+    fn warnings() {
+        let mut params = crate::params::Params::new(3);
+        params.set("validation", "lenient");
+        let solver = crate::configured_solver(3, &params).unwrap();
+        let reports = run(3, &*solver, "#1 @ 1,3: 4x4\n#1 @ 5,5: 2x2", &Part::BOTH);
+
+        let warning = "skipped line 2: claim #1 repeats the ID from line 1";
+        for report in &reports {
+            assert_eq!(report.warnings, vec![warning]);
+        }
+        assert_eq!(reports[1].answer, Ok("1".to_string()));
+        assert_eq!(reports[1].answer_type, "i32");
+        let json = reports[0].to_json().to_string();
+        assert!(json.ends_with(&format!(r#""warnings":["{}"]}}"#, warning)));
+    }
+
+    #[test]
+    fn ambiguous_json() {
+        let solver = crate::solver(3).unwrap();
+        let reports = run(3, &*solver, "#1 @ 1,3: 4x4\n#2 @ 9,9: 1x1", &[Part::Two]);
+        let json = reports[0].to_json().to_string();
+
+        assert!(json.contains(r#""answer":null,"answer_type":"i32""#));
+        assert!(json.contains(
+            r#""error":{"kind":"ambiguous","message":"ambiguous answer, could be any of 1, 2","candidates":["1","2"]}"#
+        ));
+    }
+
+    #[test]
+    fn parse_error_json() {
+        let solver = crate::solver(3).unwrap();
+        let reports = run(3, &*solver, "#1 @ 1,3 4x4", &Part::BOTH);
+        assert_eq!(reports.len(), 2);
+
+        let json = reports[1].to_json().to_string();
+        assert!(json.contains(r#""answer":null"#));
+        assert!(json.contains(
+            r#""error":{"kind":"parse","message":"day 3 input, line 1, column 9: expected ': '","#
+        ));
+        assert!(json.contains(r##""line":1,"column":9,"text":"#1 @ 1,3 4x4","expected":"': '"}"##));
+    }
+}
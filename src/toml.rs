@@ -0,0 +1,229 @@
+use std::fmt::{self, Display};
+
+use crate::error::{offset_of, LineError};
+
+// Just the TOML we use for answers.toml and config files: [table] and
+// [[array]] headers, string and integer values, and comments.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", s),
+            Value::Integer(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    // 1-based
+    pub line: usize,
+    text: String,
+    value_offset: usize,
+}
+
+impl Entry {
+    // A caret diagnostic pointing at this entry's value
+    pub fn error(&self, source: &str, expected: &str) -> String {
+        LineError::new(self.value_offset, expected)
+            .at(0, self.line, &self.text)
+            .render(source)
+    }
+
+    pub fn string(&self, source: &str) -> Result<&str, String> {
+        match &self.value {
+            Value::String(s) => Ok(s),
+            _ => Err(self.error(source, "a quoted string")),
+        }
+    }
+
+    pub fn integer(&self, source: &str) -> Result<i64, String> {
+        match &self.value {
+            Value::Integer(n) => Ok(*n),
+            _ => Err(self.error(source, "an integer")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    // None for the keys before the first header
+    pub name: Option<String>,
+    // [[name]] rather than [name]
+    pub array: bool,
+    // line of the header, 0 for the root table
+    pub line: usize,
+    pub entries: Vec<Entry>,
+}
+
+impl Table {
+    pub fn header(&self) -> String {
+        match (&self.name, self.array) {
+            (Some(name), true) => format!("[[{}]]", name),
+            (Some(name), false) => format!("[{}]", name),
+            (None, _) => "top level".to_string(),
+        }
+    }
+}
+
+// The root table comes first, even when it's empty
+pub fn parse(text: &str, source: &str) -> Result<Vec<Table>, String> {
+    let mut tables = vec![Table {
+        name: None,
+        array: false,
+        line: 0,
+        entries: vec![],
+    }];
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        let located = |e: LineError| e.within(raw, line).at(0, i + 1, raw).render(source);
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let (name, array) = parse_header(line).map_err(located)?;
+            tables.push(Table {
+                name: Some(name.to_string()),
+                array,
+                line: i + 1,
+                entries: vec![],
+            });
+            continue;
+        }
+
+        let (key, value) = parse_key_value(line).map_err(located)?;
+        let parsed = parse_value(line, value).map_err(located)?;
+        let table = tables.last_mut().expect("root table");
+        if table.entries.iter().any(|e| e.key == key) {
+            Err(located(LineError::on(line, key, "a key not already set")))?
+        }
+
+        table.entries.push(Entry {
+            key: key.to_string(),
+            value: parsed,
+            line: i + 1,
+            text: raw.to_string(),
+            value_offset: offset_of(raw, value),
+        });
+    }
+
+    Ok(tables)
+}
+
+pub fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_header(line: &str) -> Result<(&str, bool), LineError> {
+    let (inner, array) = match line.strip_prefix("[[") {
+        Some(rest) => (rest.strip_suffix("]]"), true),
+        None => (line[1..].strip_suffix(']'), false),
+    };
+    let inner = inner.ok_or_else(|| LineError::new(line.len(), "a closing ']'"))?;
+    let name = inner.trim();
+
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        Err(LineError::on(line, inner, "a table name"))?
+    }
+
+    Ok((name, array))
+}
+
+fn parse_key_value(line: &str) -> Result<(&str, &str), LineError> {
+    let eq = line
+        .find('=')
+        .ok_or_else(|| LineError::new(line.len(), "'='"))?;
+    let key = line[..eq].trim();
+    let value = line[eq + 1..].trim();
+    if key.is_empty() {
+        Err(LineError::new(0, "a key"))?
+    }
+    if value.is_empty() {
+        Err(LineError::new(line.len(), "a value"))?
+    }
+
+    Ok((key, value))
+}
+
+fn parse_value(line: &str, value: &str) -> Result<Value, LineError> {
+    if value.starts_with('"') {
+        return string(line, value).map(Value::String);
+    }
+
+    let digits = value.replace('_', "");
+    digits
+        .parse()
+        .map(Value::Integer)
+        .map_err(|_| LineError::on(line, value, "a quoted string or an integer"))
+}
+
+fn string(line: &str, value: &str) -> Result<String, LineError> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .filter(|_| value.len() >= 2)
+        .ok_or_else(|| LineError::on(line, value, "a quoted string"))?;
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('"') => result.push('"'),
+                Some('\\') => result.push('\\'),
+                _ => Err(LineError::on(line, value, "only \\\" or \\\\ escapes"))?,
+            },
+            '"' => Err(LineError::on(line, value, "a single quoted string"))?,
+            c => result.push(c),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        let text =
+            "# config\nname = \"x\"\n\n[day_7]\nworkers = 2\n\n[[answer]]\ninput = \"a \\\"b\\\"\"";
+        let tables = parse(text, "test.toml").unwrap();
+
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].entries[0].value, Value::String("x".to_string()));
+        assert_eq!(tables[1].header(), "[day_7]");
+        assert_eq!(tables[1].entries[0].value, Value::Integer(2));
+        assert_eq!(tables[2].header(), "[[answer]]");
+        assert_eq!(tables[2].entries[0].string("test.toml").unwrap(), "a \"b\"");
+    }
+
+    #[test]
+    fn value_errors() {
+        let tables = parse("[day_6]\n  threshold = \"ten\"", "test.toml").unwrap();
+        let error = tables[1].entries[0].integer("test.toml").unwrap_err();
+        assert!(error.starts_with("test.toml, line 2, column 15: expected an integer"));
+
+        let error = parse("[day_6\n", "test.toml").unwrap_err();
+        assert!(error.starts_with("test.toml, line 1, column 7: expected a closing ']'"));
+
+        let error = parse("a = 1\na = 2", "test.toml").unwrap_err();
+        assert!(error.contains("expected a key not already set"));
+    }
+}